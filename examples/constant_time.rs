@@ -0,0 +1,83 @@
+//! Wraps the operations of the constant-time divisors in functions that are
+//! never inlined, so that their machine code can be inspected.
+//!
+//! The `constant-time` job in CI builds this example in release mode with
+//! `--emit asm` and fails if any of the `ct_*` functions contains a
+//! conditional jump, a call or a hardware division instruction.
+
+use std::env;
+
+use quickdiv::{
+    ConstantTimeDivisorI128, ConstantTimeDivisorI16, ConstantTimeDivisorI32,
+    ConstantTimeDivisorI64, ConstantTimeDivisorI8, ConstantTimeDivisorIsize,
+    ConstantTimeDivisorU128, ConstantTimeDivisorU16, ConstantTimeDivisorU32,
+    ConstantTimeDivisorU64, ConstantTimeDivisorU8, ConstantTimeDivisorUsize,
+};
+
+macro_rules! wrappers {
+    ($DivisorT:ty, $BaseT:ty, $div:ident, $rem:ident, $divides:ident) => {
+        #[inline(never)]
+        pub fn $div(d: &$DivisorT, n: $BaseT) -> $BaseT {
+            d.div_of(n)
+        }
+
+        #[inline(never)]
+        pub fn $rem(d: &$DivisorT, n: $BaseT) -> $BaseT {
+            d.rem_of(n)
+        }
+
+        #[inline(never)]
+        pub fn $divides(d: &$DivisorT, n: $BaseT) -> bool {
+            d.divides(n)
+        }
+    };
+}
+
+wrappers! { ConstantTimeDivisorU8, u8, ct_div_u8, ct_rem_u8, ct_divides_u8 }
+wrappers! { ConstantTimeDivisorU16, u16, ct_div_u16, ct_rem_u16, ct_divides_u16 }
+wrappers! { ConstantTimeDivisorU32, u32, ct_div_u32, ct_rem_u32, ct_divides_u32 }
+wrappers! { ConstantTimeDivisorU64, u64, ct_div_u64, ct_rem_u64, ct_divides_u64 }
+wrappers! { ConstantTimeDivisorU128, u128, ct_div_u128, ct_rem_u128, ct_divides_u128 }
+wrappers! { ConstantTimeDivisorUsize, usize, ct_div_usize, ct_rem_usize, ct_divides_usize }
+wrappers! { ConstantTimeDivisorI8, i8, ct_div_i8, ct_rem_i8, ct_divides_i8 }
+wrappers! { ConstantTimeDivisorI16, i16, ct_div_i16, ct_rem_i16, ct_divides_i16 }
+wrappers! { ConstantTimeDivisorI32, i32, ct_div_i32, ct_rem_i32, ct_divides_i32 }
+wrappers! { ConstantTimeDivisorI64, i64, ct_div_i64, ct_rem_i64, ct_divides_i64 }
+wrappers! { ConstantTimeDivisorI128, i128, ct_div_i128, ct_rem_i128, ct_divides_i128 }
+wrappers! { ConstantTimeDivisorIsize, isize, ct_div_isize, ct_rem_isize, ct_divides_isize }
+
+macro_rules! exercise {
+    ($x:expr, $($DivisorT:ty, $BaseT:ty, $div:ident, $rem:ident, $divides:ident);+) => {
+        $(
+            let d = <$DivisorT>::new(($x | 1) as $BaseT);
+            let n = $x as $BaseT;
+            println!("{} {} {}", $div(&d, n), $rem(&d, n), $divides(&d, n));
+        )+
+    };
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn main() {
+    // Reading the operands at runtime keeps the wrappers from being
+    // evaluated at compile time.
+    let x: u64 = env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(12345);
+
+    exercise! {
+        x,
+        ConstantTimeDivisorU8, u8, ct_div_u8, ct_rem_u8, ct_divides_u8;
+        ConstantTimeDivisorU16, u16, ct_div_u16, ct_rem_u16, ct_divides_u16;
+        ConstantTimeDivisorU32, u32, ct_div_u32, ct_rem_u32, ct_divides_u32;
+        ConstantTimeDivisorU64, u64, ct_div_u64, ct_rem_u64, ct_divides_u64;
+        ConstantTimeDivisorU128, u128, ct_div_u128, ct_rem_u128, ct_divides_u128;
+        ConstantTimeDivisorUsize, usize, ct_div_usize, ct_rem_usize, ct_divides_usize;
+        ConstantTimeDivisorI8, i8, ct_div_i8, ct_rem_i8, ct_divides_i8;
+        ConstantTimeDivisorI16, i16, ct_div_i16, ct_rem_i16, ct_divides_i16;
+        ConstantTimeDivisorI32, i32, ct_div_i32, ct_rem_i32, ct_divides_i32;
+        ConstantTimeDivisorI64, i64, ct_div_i64, ct_rem_i64, ct_divides_i64;
+        ConstantTimeDivisorI128, i128, ct_div_i128, ct_rem_i128, ct_divides_i128;
+        ConstantTimeDivisorIsize, isize, ct_div_isize, ct_rem_isize, ct_divides_isize
+    }
+}
@@ -0,0 +1,158 @@
+//! Division of `num-bigint` integers by precomputed divisors.
+//!
+//! The big integer is divided limb by limb using the precomputed reciprocal of
+//! the divisor, instead of converting the divisor into a big integer first.
+//! Remainders are returned as primitive integers, since they always fit.
+
+use alloc::vec::Vec;
+use core::ops::{Div, Rem};
+use num_bigint::{BigInt, BigUint, Sign};
+
+use crate::{DivisorU32, DivisorU64};
+
+// BigUint can only be built from 32-bit digits.
+trait IntoBigUint {
+    fn into_biguint(self) -> BigUint;
+}
+
+impl IntoBigUint for Vec<u32> {
+    fn into_biguint(self) -> BigUint {
+        BigUint::new(self)
+    }
+}
+
+impl IntoBigUint for Vec<u64> {
+    #[allow(clippy::cast_possible_truncation)]
+    fn into_biguint(self) -> BigUint {
+        BigUint::new(
+            self.into_iter()
+                .flat_map(|limb| [limb as u32, (limb >> 32) as u32])
+                .collect(),
+        )
+    }
+}
+
+macro_rules! bigint_impl {
+    ($DivisorT:ident, $BaseT:ty, $SignedRemT:ty, $to_digits:ident, $iter_digits:ident) => {
+        impl Div<$DivisorT> for &BigUint {
+            type Output = BigUint;
+
+            fn div(self, rhs: $DivisorT) -> BigUint {
+                let mut limbs = self.$to_digits();
+                rhs.div_limbs_in_place(&mut limbs);
+                limbs.into_biguint()
+            }
+        }
+
+        impl Div<$DivisorT> for BigUint {
+            type Output = BigUint;
+
+            #[inline]
+            fn div(self, rhs: $DivisorT) -> BigUint {
+                &self / rhs
+            }
+        }
+
+        impl Rem<$DivisorT> for &BigUint {
+            type Output = $BaseT;
+
+            #[inline]
+            fn rem(self, rhs: $DivisorT) -> $BaseT {
+                rhs.rem_limbs_iter(self.$iter_digits().rev())
+            }
+        }
+
+        impl Rem<$DivisorT> for BigUint {
+            type Output = $BaseT;
+
+            #[inline]
+            fn rem(self, rhs: $DivisorT) -> $BaseT {
+                &self % rhs
+            }
+        }
+
+        // Like the built-in operators, the quotient is rounded towards zero and
+        // the remainder has the same sign as the dividend.
+        impl Div<$DivisorT> for &BigInt {
+            type Output = BigInt;
+
+            fn div(self, rhs: $DivisorT) -> BigInt {
+                BigInt::from_biguint(self.sign(), self.magnitude() / rhs)
+            }
+        }
+
+        impl Div<$DivisorT> for BigInt {
+            type Output = BigInt;
+
+            #[inline]
+            fn div(self, rhs: $DivisorT) -> BigInt {
+                &self / rhs
+            }
+        }
+
+        impl Rem<$DivisorT> for &BigInt {
+            type Output = $SignedRemT;
+
+            fn rem(self, rhs: $DivisorT) -> $SignedRemT {
+                let r = (self.magnitude() % rhs) as $SignedRemT;
+                if self.sign() == Sign::Minus {
+                    -r
+                } else {
+                    r
+                }
+            }
+        }
+
+        impl Rem<$DivisorT> for BigInt {
+            type Output = $SignedRemT;
+
+            #[inline]
+            fn rem(self, rhs: $DivisorT) -> $SignedRemT {
+                &self % rhs
+            }
+        }
+    };
+}
+
+bigint_impl! { DivisorU32, u32, i64, to_u32_digits, iter_u32_digits }
+bigint_impl! { DivisorU64, u64, i128, to_u64_digits, iter_u64_digits }
+
+macro_rules! bigint_tests {
+    ($SelfT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        mod $ModName {
+            use num_bigint::{BigInt, BigUint, Sign};
+            use quickcheck::*;
+            use std::vec::Vec;
+
+            use super::*;
+
+            quickcheck! {
+                fn biguint_agrees_with_num_bigint(digits: Vec<u32>, d: $SelfT) -> bool {
+                    let n = BigUint::new(digits);
+                    let expected_rem = &n % d.get();
+
+                    &n / d == &n / d.get()
+                        && BigUint::from(&n % d) == expected_rem
+                        && n.clone() / d == n.clone() / d.get()
+                        && BigUint::from(n % d) == expected_rem
+                }
+            }
+
+            quickcheck! {
+                fn bigint_agrees_with_num_bigint(negative: bool, digits: Vec<u32>, d: $SelfT) -> bool {
+                    let sign = if negative { Sign::Minus } else { Sign::Plus };
+                    let n = BigInt::new(sign, digits);
+
+                    &n / d == &n / d.get()
+                        && BigInt::from(&n % d) == &n % d.get()
+                        && n.clone() / d == n.clone() / d.get()
+                        && BigInt::from(n.clone() % d) == n % d.get()
+                }
+            }
+        }
+    };
+}
+
+bigint_tests! { DivisorU32, bigint_u32 }
+bigint_tests! { DivisorU64, bigint_u64 }
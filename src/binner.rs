@@ -0,0 +1,384 @@
+macro_rules! declare_binner_struct {
+    ($SelfT:ident, $DivisorT:ident, $BaseT:ty, $UnsignedBaseT:ty) => {
+        /// Maps values into equal-width histogram bins, using a precomputed
+        /// divisor for the width of the bins.
+        ///
+        /// The regular bins cover `min..max`, where `max = min + bins * width`
+        /// may be one past the largest value of the type. Values below `min` go
+        /// into the underflow bin, and values at or above `max` into the
+        /// overflow bin, so a histogram needs `bins + 2` counters:
+        ///
+        /// - index `0` is the underflow bin,
+        /// - index `i + 1` is the `i`-th regular bin, covering
+        ///   `min + i * width..min + (i + 1) * width`,
+        /// - index `bins + 1` is the overflow bin.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        /// // Four bins of width 10, covering 20..60.
+        #[doc = concat!("let binner = ", stringify!($SelfT), "::new(20, 10, 4);")]
+        /// assert_eq!(binner.bin_of(5), 0);
+        /// assert_eq!(binner.bin_of(20), 1);
+        /// assert_eq!(binner.bin_of(59), 4);
+        /// assert_eq!(binner.bin_of(60), 5);
+        /// assert_eq!(binner.bin_range(2), Some(30..=39));
+        /// ```
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub struct $SelfT {
+            min: $BaseT,
+            width: $DivisorT,
+            bins: usize,
+            // bins * width - 1, the offset from min of the last value in the
+            // regular bins, which fits even if max does not.
+            last: $UnsignedBaseT,
+        }
+
+        impl $SelfT {
+            /// Index of the underflow bin.
+            pub const UNDERFLOW: usize = 0;
+
+            /// Creates a binner with `bins` regular bins of the given `width`,
+            /// starting at `min`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `width` or `bins` equals zero, or if the last regular
+            /// bin extends past
+            #[doc = concat!("`", stringify!($BaseT), "::MAX`.")]
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let binner = ", stringify!($SelfT), "::new(0, 25, 4);")]
+            /// assert_eq!(binner.max(), Some(100));
+            ///
+            /// // The last bin may end with the largest value of the type.
+            #[doc = concat!("let binner = ", stringify!($SelfT), "::new(", stringify!($BaseT), "::MAX - 99, 25, 4);")]
+            /// assert_eq!(binner.max(), None);
+            #[doc = concat!("assert_eq!(binner.bin_of(", stringify!($BaseT), "::MAX), 4);")]
+            /// ```
+            pub fn new(min: $BaseT, width: $UnsignedBaseT, bins: usize) -> Self {
+                use core::convert::TryFrom;
+
+                let width = <$DivisorT>::new(width);
+                assert!(bins > 0, "expected at least one bin");
+                assert!(bins < usize::MAX, "too many bins");
+
+                let last = <$UnsignedBaseT>::try_from(bins - 1)
+                    .ok()
+                    .and_then(|full| full.checked_mul(width.get()))
+                    .and_then(|start| start.checked_add(width.get() - 1));
+                let last = match last {
+                    Some(last) if last <= Self::room(min) => last,
+                    _ => panic!(concat!("end of the last bin does not fit into ", stringify!($BaseT))),
+                };
+
+                Self {
+                    min,
+                    width,
+                    bins,
+                    last,
+                }
+            }
+
+            /// Creates a binner with `bins` regular bins of equal width covering
+            /// `min..max`.
+            ///
+            /// The width is the span rounded up to a multiple of `bins`, so
+            /// [`max`](Self::max) may lie past the requested `max` when the span
+            /// is not a multiple of `bins`. Use [`new`](Self::new) to choose the
+            /// width exactly.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `min` is not less than `max`, if `bins` equals zero, or
+            /// if the rounded-up last regular bin extends past
+            #[doc = concat!("`", stringify!($BaseT), "::MAX`.")]
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let binner = ", stringify!($SelfT), "::from_range(20, 60, 4);")]
+            #[doc = concat!("assert_eq!(binner, ", stringify!($SelfT), "::new(20, 10, 4));")]
+            ///
+            #[doc = concat!("let binner = ", stringify!($SelfT), "::from_range(0, 10, 4);")]
+            /// assert_eq!((binner.width(), binner.max()), (3, Some(12)));
+            /// ```
+            #[allow(clippy::cast_sign_loss)]
+            pub fn from_range(min: $BaseT, max: $BaseT, bins: usize) -> Self {
+                use core::convert::TryFrom;
+
+                assert!(min < max, "expected min to be less than max");
+                assert!(bins > 0, "expected at least one bin");
+
+                let span = (max as $UnsignedBaseT).wrapping_sub(min as $UnsignedBaseT);
+                // More bins than the type can count are more than the span, so
+                // each of them is a single value wide.
+                let width = <$UnsignedBaseT>::try_from(bins).map_or(1, |bins| (span - 1) / bins + 1);
+                Self::new(min, width, bins)
+            }
+
+            /// Returns the start of the first regular bin.
+            #[inline]
+            pub const fn min(&self) -> $BaseT {
+                self.min
+            }
+
+            /// Returns the end of the last regular bin, which is excluded from
+            /// it, or `None` if the last regular bin ends with
+            #[doc = concat!("`", stringify!($BaseT), "::MAX`.")]
+            #[inline]
+            #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+            pub const fn max(&self) -> Option<$BaseT> {
+                if self.last == Self::room(self.min) {
+                    None
+                } else {
+                    Some((self.min as $UnsignedBaseT).wrapping_add(self.last).wrapping_add(1) as $BaseT)
+                }
+            }
+
+            /// Returns the width of every regular bin.
+            #[inline]
+            pub const fn width(&self) -> $UnsignedBaseT {
+                self.width.get()
+            }
+
+            /// Returns the number of regular bins.
+            #[inline]
+            pub const fn bins(&self) -> usize {
+                self.bins
+            }
+
+            /// Returns the index of the overflow bin, which is `bins + 1`.
+            #[inline]
+            pub const fn overflow(&self) -> usize {
+                self.bins + 1
+            }
+
+            /// Returns the index of the bin containing `v`, clamping values
+            /// outside `min..max` to the underflow and overflow bins.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let binner = ", stringify!($SelfT), "::new(20, 20, 4);")]
+            #[doc = concat!("assert_eq!(binner.bin_of(", stringify!($BaseT), "::MIN), ", stringify!($SelfT), "::UNDERFLOW);")]
+            /// assert_eq!(binner.bin_of(99), 4);
+            #[doc = concat!("assert_eq!(binner.bin_of(", stringify!($BaseT), "::MAX), binner.overflow());")]
+            /// ```
+            #[inline]
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            pub fn bin_of(&self, v: $BaseT) -> usize {
+                if v < self.min {
+                    return Self::UNDERFLOW;
+                }
+
+                let offset = (v as $UnsignedBaseT).wrapping_sub(self.min as $UnsignedBaseT);
+                if offset > self.last {
+                    self.overflow()
+                } else {
+                    // Less than `bins`, so the cast is lossless.
+                    self.width.div_of(offset) as usize + 1
+                }
+            }
+
+            /// Returns the values covered by the regular bin at `index`, or
+            /// `None` if `index` is the underflow bin, the overflow bin or out
+            /// of bounds.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let binner = ", stringify!($SelfT), "::new(0, 25, 4);")]
+            /// assert_eq!(binner.bin_range(1), Some(0..=24));
+            /// assert_eq!(binner.bin_range(4), Some(75..=99));
+            /// assert_eq!(binner.bin_range(0), None);
+            /// assert_eq!(binner.bin_range(5), None);
+            /// ```
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+            pub fn bin_range(&self, index: usize) -> Option<core::ops::RangeInclusive<$BaseT>> {
+                if index == Self::UNDERFLOW || index > self.bins {
+                    return None;
+                }
+
+                let width = self.width.get();
+                let start = (self.min as $UnsignedBaseT).wrapping_add((index - 1) as $UnsignedBaseT * width);
+                Some(start as $BaseT..=start.wrapping_add(width - 1) as $BaseT)
+            }
+
+            /// Counts the bins of all `values`, adding them into `counts`,
+            /// which holds one counter for every bin including the underflow
+            /// and overflow bins.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `counts` does not hold exactly `bins + 2` counters.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let binner = ", stringify!($SelfT), "::new(0, 25, 4);")]
+            /// let mut counts = [0; 6];
+            /// binner.bin_slice(&[3, 30, 30, 99, 100, 120], &mut counts);
+            /// assert_eq!(counts, [0, 1, 2, 0, 1, 2]);
+            /// ```
+            pub fn bin_slice(&self, values: &[$BaseT], counts: &mut [u64]) {
+                assert!(
+                    counts.len().checked_sub(2) == Some(self.bins),
+                    "expected one counter for every bin"
+                );
+
+                for &v in values {
+                    counts[self.bin_of(v)] += 1;
+                }
+            }
+
+            // The largest offset from `min` that fits into the type.
+            #[allow(clippy::cast_sign_loss)]
+            const fn room(min: $BaseT) -> $UnsignedBaseT {
+                (<$BaseT>::MAX as $UnsignedBaseT).wrapping_sub(min as $UnsignedBaseT)
+            }
+        }
+    };
+}
+
+macro_rules! binner_tests {
+    ($SelfT:ident, $BaseT:ident, $UnsignedBaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        mod $ModName {
+            use quickcheck::*;
+            use std::vec::Vec;
+
+            use super::*;
+
+            fn reference_bin_of(binner: &$SelfT, v: $BaseT) -> usize {
+                let offset = (v as $UnsignedBaseT).wrapping_sub(binner.min() as $UnsignedBaseT);
+                if v < binner.min() {
+                    0
+                } else if binner.max().map_or(false, |max| v >= max) {
+                    binner.bins() + 1
+                } else {
+                    (offset / binner.width()) as usize + 1
+                }
+            }
+
+            #[test]
+            #[should_panic(expected = "attempt to divide by zero")]
+            fn rejects_zero_width() {
+                let _ = $SelfT::new(0, 0, 1);
+            }
+
+            #[test]
+            #[should_panic(expected = "expected at least one bin")]
+            fn rejects_zero_bins() {
+                let _ = $SelfT::new(0, 1, 0);
+            }
+
+            #[test]
+            #[should_panic(expected = "expected min to be less than max")]
+            fn rejects_empty_range() {
+                let _ = $SelfT::from_range(5, 5, 1);
+            }
+
+            #[test]
+            #[should_panic(expected = "end of the last bin does not fit")]
+            fn rejects_overflowing_bins() {
+                let _ = $SelfT::new(<$BaseT>::MAX - 1, 3, 1);
+            }
+
+            #[test]
+            #[should_panic(expected = "expected one counter for every bin")]
+            fn rejects_wrong_number_of_counters() {
+                $SelfT::new(0, 1, 4).bin_slice(&[0], &mut [0; 5]);
+            }
+
+            #[test]
+            fn covers_full_range() {
+                let binner = $SelfT::new(<$BaseT>::MIN, <$UnsignedBaseT>::MAX, 1);
+                assert_eq!(binner.bin_of(<$BaseT>::MIN), 1);
+                assert_eq!(binner.bin_of(<$BaseT>::MAX - 1), 1);
+                assert_eq!(binner.bin_of(<$BaseT>::MAX), binner.overflow());
+                assert_eq!(binner.bin_range(1), Some(<$BaseT>::MIN..=<$BaseT>::MAX - 1));
+                assert_eq!(binner.max(), Some(<$BaseT>::MAX));
+            }
+
+            #[test]
+            fn bins_may_end_with_max() {
+                let width = (<$UnsignedBaseT>::MAX >> 2) + 1;
+                let binner = $SelfT::new(<$BaseT>::MIN, width, 4);
+                assert_eq!(binner.max(), None);
+                assert_eq!(binner.bin_of(<$BaseT>::MIN), 1);
+                assert_eq!(binner.bin_of(<$BaseT>::MAX), 4);
+
+                let last_start = (<$BaseT>::MIN as $UnsignedBaseT).wrapping_add(3 * width) as $BaseT;
+                assert_eq!(binner.bin_range(4), Some(last_start..=<$BaseT>::MAX));
+                assert_eq!(binner.bin_of(last_start), 4);
+                assert_eq!(binner.bin_of(last_start - 1), 3);
+
+                let binner = $SelfT::new(<$BaseT>::MAX, 1, 1);
+                assert_eq!(binner.max(), None);
+                assert_eq!(binner.bin_of(<$BaseT>::MAX), 1);
+                assert_eq!(binner.bin_range(1), Some(<$BaseT>::MAX..=<$BaseT>::MAX));
+            }
+
+            quickcheck! {
+                fn bins_agree_with_reference(min: $BaseT, width: $UnsignedBaseT, bins: u8, v: $BaseT) -> bool {
+                    let min = min.min(<$BaseT>::MAX - 64);
+                    let binner = $SelfT::new(min, width % 4 + 1, bins as usize % 16 + 1);
+
+                    let index = binner.bin_of(v);
+                    index == reference_bin_of(&binner, v)
+                        && binner.bin_range(index).map_or(true, |range| range.contains(&v))
+                }
+            }
+
+            quickcheck! {
+                fn wide_bins_agree_with_reference(width: $UnsignedBaseT, v: $BaseT) -> bool {
+                    let binner = $SelfT::new(<$BaseT>::MIN, width.max(1), 1);
+                    binner.bin_of(v) == reference_bin_of(&binner, v)
+                }
+            }
+
+            quickcheck! {
+                fn from_range_covers_range(min: $BaseT, max: $BaseT, bins: u8) -> TestResult {
+                    let (min, max) = (min.max(<$BaseT>::MIN / 2), max.min(<$BaseT>::MAX / 2));
+                    if min >= max {
+                        return TestResult::discard();
+                    }
+                    let bins = bins as usize % 16 + 1;
+                    let binner = $SelfT::from_range(min, max, bins);
+
+                    let span = (max as $UnsignedBaseT).wrapping_sub(min as $UnsignedBaseT);
+                    let width = binner.width();
+                    TestResult::from_bool(
+                        binner.min() == min
+                            && binner.bins() == bins
+                            && (width - 1) * (bins as $UnsignedBaseT) < span
+                            && binner.bin_of(max - 1) <= bins
+                            && binner.bin_of(min) == 1,
+                    )
+                }
+            }
+
+            quickcheck! {
+                fn bin_slice_counts_every_value(values: Vec<$BaseT>, min: $BaseT) -> bool {
+                    let binner = $SelfT::new(min.min(<$BaseT>::MAX - 30), 10, 3);
+                    let mut counts = [0; 5];
+                    binner.bin_slice(&values, &mut counts);
+
+                    (0..5).all(|index| {
+                        counts[index] == values.iter().filter(|&&v| binner.bin_of(v) == index).count() as u64
+                    })
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,146 @@
+macro_rules! declare_cache_struct {
+    ($SelfT:ident, $DivisorT:ident, $BaseT:ty, $UnsignedBaseT:ty) => {
+        #[doc = concat!("Fixed-capacity cache of [`", stringify!($DivisorT), "`]s, keyed by the value of the divisor.")]
+        ///
+        /// The cache is direct-mapped: each value `d` is hashed to one of `N`
+        /// slots, and a divisor for `d` is only built when that slot holds a
+        /// different divisor (or none at all), in which case it is replaced. This
+        /// makes lookups cheap and predictable, and works well for a small working
+        /// set of divisors that recur in an unpredictable order.
+        ///
+        /// The cache itself does not divide: lookups return copies of the full
+        #[doc = concat!("[`", stringify!($DivisorT), "`], so all of its methods, including `mul_div`")]
+        /// and the multiples helpers, are available on the result.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let mut cache = ", stringify!($SelfT), "::<8>::new();")]
+        ///
+        /// for &(n, buckets) in &[(100, 7), (45, 13), (64, 7)] {
+        ///     let d = cache.get_or_insert(buckets);
+        ///     assert_eq!(n % d, n % buckets);
+        /// }
+        /// ```
+        #[derive(Clone, Copy)]
+        pub struct $SelfT<const N: usize> {
+            slots: [Option<$DivisorT>; N],
+        }
+
+        impl<const N: usize> $SelfT<N> {
+            /// Creates an empty cache.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `N` equals zero.
+            pub const fn new() -> Self {
+                // Forces a panic when N = 0, since we cannot use panic! in const.
+                let _ = 1 / N;
+
+                Self { slots: [None; N] }
+            }
+
+            /// Returns the cached divisor for `d`, building and storing it first
+            /// if it is not in the cache.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `d` equals zero.
+            #[inline]
+            pub fn get_or_insert(&mut self, d: $BaseT) -> $DivisorT {
+                let slot = &mut self.slots[Self::slot(d)];
+                match *slot {
+                    Some(divisor) if divisor.get() == d => divisor,
+                    _ => {
+                        let divisor = <$DivisorT>::new(d);
+                        *slot = Some(divisor);
+                        divisor
+                    }
+                }
+            }
+
+            /// Returns the cached divisor for `d`, if there is one.
+            #[inline]
+            pub fn get(&self, d: $BaseT) -> Option<$DivisorT> {
+                match self.slots[Self::slot(d)] {
+                    Some(divisor) if divisor.get() == d => Some(divisor),
+                    _ => None,
+                }
+            }
+
+            /// Removes all divisors from the cache.
+            pub fn clear(&mut self) {
+                self.slots = [None; N];
+            }
+
+            // Fibonacci hashing, so that runs of consecutive divisors spread
+            // evenly over the slots.
+            #[inline]
+            #[allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            fn slot(d: $BaseT) -> usize {
+                let key = (d as $UnsignedBaseT) as u128;
+                let key = (key ^ (key >> 64)) as u64;
+                (key.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 32) as usize % N
+            }
+        }
+
+        impl<const N: usize> Default for $SelfT<N> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<const N: usize> core::fmt::Debug for $SelfT<N> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_list()
+                    .entries(self.slots.iter().filter_map(|slot| *slot))
+                    .finish()
+            }
+        }
+    };
+}
+
+macro_rules! cache_tests {
+    ($CacheT:ident, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        mod $ModName {
+            use quickcheck::*;
+            use std::vec::Vec;
+
+            use super::*;
+
+            #[test]
+            #[should_panic(expected = "attempt to divide by zero")]
+            fn cannot_create_empty_caches() {
+                let _ = <$CacheT<0>>::new();
+            }
+
+            #[test]
+            fn starts_empty() {
+                let cache = <$CacheT<4>>::new();
+                assert!(cache.get(1).is_none());
+            }
+
+            quickcheck! {
+                fn returns_divisor_for_requested_value(ds: Vec<$BaseT>) -> bool {
+                    let mut cache = <$CacheT<4>>::new();
+                    ds.into_iter()
+                        .filter(|&d| d != 0)
+                        .all(|d| cache.get_or_insert(d).get() == d && cache.get(d).is_some())
+                }
+            }
+
+            quickcheck! {
+                fn clear_removes_everything(ds: Vec<$BaseT>) -> bool {
+                    let mut cache = <$CacheT<4>>::new();
+                    for &d in ds.iter().filter(|&&d| d != 0) {
+                        cache.get_or_insert(d);
+                    }
+                    cache.clear();
+                    ds.iter().all(|&d| cache.get(d).is_none())
+                }
+            }
+        }
+    };
+}
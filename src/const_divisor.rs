@@ -0,0 +1,408 @@
+macro_rules! declare_const_struct {
+    ($SelfT:ident, $DivisorT:ident, $BaseT:ty, $SIZE:literal) => {
+        /// Divisor for division and modulo operations by
+        #[doc = concat!($SIZE)]
+        /// integer values, whose value `D` is known at compile time.
+        ///
+        /// This lets the compiler apply its own optimizations for division by a
+        /// constant, which can be even faster than a precomputed divisor (most
+        /// notably for divisibility checks), while providing the same division,
+        /// `mul_div` and multiples interface
+        #[doc = concat!("as [`", stringify!($DivisorT), "`].")]
+        /// The remaining methods, such as splitting or digit extraction, are
+        /// available on the runtime divisor returned by
+        /// [`to_divisor`](Self::to_divisor). Using `D = 0` is a compile-time error.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::{", stringify!($SelfT), ", ", stringify!($DivisorT), "};")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<7>::new();")]
+        /// assert_eq!(100 / d, 14);
+        ///
+        #[doc = concat!("let runtime: ", stringify!($DivisorT), " = d.into();")]
+        /// assert_eq!(100 % runtime, 100 % d);
+        /// ```
+        ///
+        /// ```compile_fail
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<0>::new();")]
+        /// ```
+        #[derive(Clone, Copy)]
+        pub struct $SelfT<const D: $BaseT> {
+            _private: (),
+        }
+
+        impl<const D: $BaseT> $SelfT<D> {
+            // Fails to evaluate, and therefore to compile, when D = 0.
+            const NONZERO: $BaseT = 1 / D;
+            // Built once at compile time, for the methods that forward to the
+            // runtime divisor.
+            const DIVISOR: $DivisorT = <$DivisorT>::new(D);
+
+            const_impl! { $SelfT, $DivisorT, $BaseT }
+            slice_impl! { $SelfT, $BaseT, concat!(stringify!($SelfT), "::<3>::new()") }
+        }
+
+        impl<const D: $BaseT> Default for $SelfT<D> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<const D: $BaseT> From<$SelfT<D>> for $DivisorT {
+            #[inline]
+            fn from(d: $SelfT<D>) -> $DivisorT {
+                d.to_divisor()
+            }
+        }
+
+        impl_traits! { [const D: $BaseT], $SelfT<D>, $BaseT }
+    };
+}
+
+macro_rules! const_impl {
+    ($SelfT:ident, $DivisorT:ident, $BaseT:ty) => {
+        /// Creates a divisor which can be used for faster computation of division and modulo by `D`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<42>::new();")]
+        /// ```
+        #[inline]
+        pub const fn new() -> $SelfT<D> {
+            let _ = Self::NONZERO;
+
+            Self { _private: () }
+        }
+
+        /// Returns the value of `D` as a primitive type.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<7>::new();")]
+        /// assert_eq!(d.get(), 7);
+        /// ```
+        #[inline]
+        pub const fn get(&self) -> $BaseT {
+            D
+        }
+
+        /// Returns `true` if `n` is divisible by `self`.
+        ///
+        /// We take `0` to be divisible by all non-zero numbers.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<17>::new();")]
+        /// assert!(d.divides(34));
+        /// ```
+        #[inline]
+        pub const fn divides(&self, n: $BaseT) -> bool {
+            self.rem_of(n) == 0
+        }
+
+        /// Returns the remainder of dividing `n` by `self`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<11>::new();")]
+        /// let rem = d.rem_of(30);
+        /// assert_eq!(rem, 8);
+        /// ```
+        #[inline]
+        pub const fn rem_of(&self, n: $BaseT) -> $BaseT {
+            n.wrapping_rem(D)
+        }
+
+        /// Returns the result of dividing `n` by `self`.
+        ///
+        /// Overflow is handled in the same way as by
+        #[doc = concat!("[`", stringify!($DivisorT), "::div_of`].")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<17>::new();")]
+        /// let div = d.div_of(34);
+        /// assert_eq!(div, 2);
+        /// ```
+        #[inline]
+        pub const fn div_of(&self, n: $BaseT) -> $BaseT {
+            n.wrapping_div(D)
+        }
+
+        /// Returns the result of dividing `n` by `self`, provided that `n` is a
+        /// multiple of `self`.
+        ///
+        /// If `n` is not a multiple of `self`, the result is unspecified.
+        ///
+        /// # Panics
+        ///
+        /// Panics in debug builds if `n` is not divisible by `self`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<12>::new();")]
+        /// assert_eq!(d.div_exact(108), 9);
+        /// ```
+        #[inline]
+        #[allow(clippy::cast_lossless)]
+        pub const fn div_exact(&self, n: $BaseT) -> $BaseT {
+            // Forces a panic in debug builds when n is not a multiple of D,
+            // since we cannot use panic! in const.
+            #[cfg(debug_assertions)]
+            let _ = 1 / (self.divides(n) as $BaseT);
+
+            n.wrapping_div(D)
+        }
+
+        /// Converts this divisor into the equivalent runtime divisor.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::{", stringify!($SelfT), ", ", stringify!($DivisorT), "};")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<9>::new().to_divisor();")]
+        #[doc = concat!("assert_eq!(d, ", stringify!($DivisorT), "::new(9));")]
+        /// ```
+        #[inline]
+        pub const fn to_divisor(self) -> $DivisorT {
+            Self::DIVISOR
+        }
+    };
+}
+
+macro_rules! const_unsigned_impl {
+    ($SelfT:ident, $BaseT:ty) => {
+        /// Returns both the quotient and the remainder of dividing `n` by
+        /// `self`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<11>::new();")]
+        /// assert_eq!(d.div_rem_of(30), (2, 8));
+        /// ```
+        #[inline]
+        pub const fn div_rem_of(&self, n: $BaseT) -> ($BaseT, $BaseT) {
+            (n / D, n % D)
+        }
+    };
+}
+
+macro_rules! const_forward_impl {
+    ($SelfT:ident, $DivisorT:ident, $MultiplesT:ident, $BaseT:ty, $FloorT:ty, $CountT:ty) => {
+        /// Returns `a * b / self`, rounded towards zero, or `None` if the result
+        /// does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        #[doc = concat!("See [`", stringify!($DivisorT), "::mul_div`].")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<4>::new();")]
+        /// assert_eq!(d.mul_div(3, 5), Some(3));
+        /// ```
+        #[inline]
+        pub fn mul_div(&self, a: $BaseT, b: $BaseT) -> Option<$BaseT> {
+            Self::DIVISOR.mul_div(a, b)
+        }
+
+        /// Returns `a * b / self`, rounded according to `mode`, or `None` if the
+        /// result does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        #[doc = concat!("See [`", stringify!($DivisorT), "::mul_div_round`].")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::{", stringify!($SelfT), ", RoundingMode};")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<4>::new();")]
+        /// assert_eq!(d.mul_div_round(3, 5, RoundingMode::Ceil), Some(4));
+        /// ```
+        #[inline]
+        pub fn mul_div_round(&self, a: $BaseT, b: $BaseT, mode: RoundingMode) -> Option<$BaseT> {
+            Self::DIVISOR.mul_div_round(a, b, mode)
+        }
+
+        /// Returns `a * b % self`, computed without overflow.
+        ///
+        #[doc = concat!("See [`", stringify!($DivisorT), "::mul_rem`].")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<4>::new();")]
+        /// assert_eq!(d.mul_rem(3, 5), 3);
+        /// ```
+        #[inline]
+        pub fn mul_rem(&self, a: $BaseT, b: $BaseT) -> $BaseT {
+            Self::DIVISOR.mul_rem(a, b)
+        }
+
+        /// Returns the largest multiple of `self` that is less than or equal to
+        /// `n`.
+        ///
+        #[doc = concat!("See [`", stringify!($DivisorT), "::floor_to_multiple`].")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<15>::new();")]
+        /// assert_eq!(d.floor_to_multiple(100), d.to_divisor().floor_to_multiple(100));
+        /// ```
+        #[inline]
+        pub const fn floor_to_multiple(&self, n: $BaseT) -> $FloorT {
+            Self::DIVISOR.floor_to_multiple(n)
+        }
+
+        /// Returns the smallest multiple of `self` that is greater than or equal
+        /// to `n`, or `None` if it does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<10>::new();")]
+        /// assert_eq!(d.ceil_to_multiple(95), Some(100));
+        #[doc = concat!("assert_eq!(d.ceil_to_multiple(", stringify!($BaseT), "::MAX), None);")]
+        /// ```
+        #[inline]
+        pub const fn ceil_to_multiple(&self, n: $BaseT) -> Option<$BaseT> {
+            Self::DIVISOR.ceil_to_multiple(n)
+        }
+
+        /// Returns the smallest multiple of `self` that is strictly greater than
+        /// `n`, or `None` if it does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<10>::new();")]
+        /// assert_eq!(d.next_multiple_after(90), Some(100));
+        /// ```
+        #[inline]
+        pub const fn next_multiple_after(&self, n: $BaseT) -> Option<$BaseT> {
+            Self::DIVISOR.next_multiple_after(n)
+        }
+
+        /// Returns the number of multiples of `self` within `range`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<15>::new();")]
+        /// assert_eq!(d.count_multiples_in(20..70), 3);
+        /// ```
+        #[inline]
+        pub fn count_multiples_in(&self, range: core::ops::Range<$BaseT>) -> $CountT {
+            Self::DIVISOR.count_multiples_in(range)
+        }
+
+        /// Returns an iterator over the multiples of `self` within `range`, in
+        /// increasing order.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::<15>::new();")]
+        /// assert!(d.multiples_in(20..70).eq([30, 45, 60]));
+        /// ```
+        #[inline]
+        pub fn multiples_in(&self, range: core::ops::Range<$BaseT>) -> $MultiplesT {
+            Self::DIVISOR.multiples_in(range)
+        }
+    };
+}
+
+macro_rules! const_divisor_tests {
+    ($SelfT:ident, $DivisorT:ident, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        mod $ModName {
+            use quickcheck::*;
+            use std::vec::Vec;
+
+            use super::*;
+
+            fn agrees_with_runtime<const D: $BaseT>(x: $BaseT) -> bool {
+                let d = <$SelfT<D>>::new();
+                let runtime = <$DivisorT>::new(D);
+
+                d.get() == D
+                    && d.to_divisor() == runtime
+                    && x / d == x / runtime
+                    && x % d == x % runtime
+                    && d.divides(x) == runtime.divides(x)
+                    && d.div_exact(x - x % d) == runtime.div_exact(x - x % runtime)
+            }
+
+            quickcheck! {
+                fn agrees_with_runtime_divisor(x: $BaseT) -> bool {
+                    agrees_with_runtime::<1>(x)
+                        && agrees_with_runtime::<3>(x)
+                        && agrees_with_runtime::<8>(x)
+                        && agrees_with_runtime::<{ !1 }>(x)
+                        && agrees_with_runtime::<{ !($BaseT::MAX >> 1) }>(x)
+                        && agrees_with_runtime::<{ $BaseT::MAX }>(x)
+                }
+            }
+
+            fn forwards_to_runtime<const D: $BaseT>(a: $BaseT, b: $BaseT) -> bool {
+                let d = <$SelfT<D>>::new();
+                let runtime = <$DivisorT>::new(D);
+                let range = a.min(b)..a.max(b);
+
+                d.mul_div(a, b) == runtime.mul_div(a, b)
+                    && d.mul_div_round(a, b, RoundingMode::Nearest)
+                        == runtime.mul_div_round(a, b, RoundingMode::Nearest)
+                    && d.mul_rem(a, b) == runtime.mul_rem(a, b)
+                    && d.floor_to_multiple(a) == runtime.floor_to_multiple(a)
+                    && d.ceil_to_multiple(a) == runtime.ceil_to_multiple(a)
+                    && d.next_multiple_after(a) == runtime.next_multiple_after(a)
+                    && d.count_multiples_in(range.clone())
+                        == runtime.count_multiples_in(range.clone())
+                    && d.multiples_in(range.clone()).next() == runtime.multiples_in(range).next()
+            }
+
+            quickcheck! {
+                fn forwarded_methods_agree_with_runtime_divisor(a: $BaseT, b: $BaseT) -> bool {
+                    forwards_to_runtime::<1>(a, b)
+                        && forwards_to_runtime::<3>(a, b)
+                        && forwards_to_runtime::<{ !($BaseT::MAX >> 1) }>(a, b)
+                        && forwards_to_runtime::<{ $BaseT::MAX }>(a, b)
+                }
+            }
+
+            quickcheck! {
+                fn count_divisible_agrees_with_runtime_divisor(ns: Vec<$BaseT>) -> bool {
+                    let d = <$SelfT<5>>::new();
+                    d.count_divisible(&ns) == <$DivisorT>::from(d).count_divisible(&ns)
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,487 @@
+//! Divisors whose execution time does not depend on the dividend.
+//!
+//! The tests below only check that the results agree with the built-in
+//! operators. That the generated code has no branches, calls or hardware
+//! division instructions is checked by the `constant-time` job in
+//! `.github/workflows/check.yml`, which inspects the assembly of
+//! `examples/constant_time.rs`. Run it locally after changing this file:
+//! build the assembly with
+//! `cargo rustc --release --example constant_time -- --emit asm` and run the
+//! `awk` script from that job over `target/release/examples/constant_time-*.s`.
+
+macro_rules! declare_constant_time_unsigned_struct {
+    ($SelfT:ident, $BaseT:ty, $SIZE:literal) => {
+        /// Divisor for division and modulo operations by
+        #[doc = concat!($SIZE)]
+        /// unsigned integer values, whose execution time does not depend on either
+        /// the dividend or the divisor.
+        ///
+        /// Unlike the regular divisors, which select one of several strategies
+        /// depending on the divisor, this type always runs the same sequence of
+        /// multiplications, subtractions and shifts in `div_of`, `rem_of` and
+        /// `divides`, with all of the divisor-specific behavior encoded in the
+        /// precomputed magic number and shift amounts. This makes it suitable for
+        /// reducing secret values, at the cost of being somewhat slower for
+        /// divisors that the regular divisors can handle with a single shift.
+        ///
+        /// Note that constructing the divisor is *not* constant-time, as it uses
+        /// hardware division.
+        ///
+        /// Only `div_of`, `rem_of`, `divides` and `div_exact` are provided, as
+        /// the other methods of the regular divisors, such as `mul_div` or the
+        /// multiples helpers, branch on their inputs. For non-secret values, use a
+        /// regular divisor built from [`get`](Self::get) instead.
+        #[derive(Clone, Copy)]
+        pub struct $SelfT {
+            d: $BaseT,
+            magic: $BaseT,
+            pre_shift: u8,
+            post_shift: u8,
+            odd_shift: u8,
+            inverse: $BaseT,
+        }
+    };
+}
+
+macro_rules! declare_constant_time_signed_struct {
+    ($SelfT:ident, $BaseT:ty, $SIZE:literal) => {
+        /// Divisor for division and modulo operations by
+        #[doc = concat!($SIZE)]
+        /// signed integer values, whose execution time does not depend on either
+        /// the dividend or the divisor.
+        ///
+        /// Unlike the regular divisors, which select one of several strategies
+        /// depending on the divisor and correct the quotient with a branch on its
+        /// sign, this type always runs the same sequence of multiplications,
+        /// additions, shifts and masks in `div_of`, `rem_of` and `divides`. This
+        /// makes it suitable for reducing secret values, at the cost of being
+        /// somewhat slower than the regular divisors.
+        ///
+        /// Note that constructing the divisor is *not* constant-time, as it uses
+        /// hardware division.
+        ///
+        /// Only `div_of`, `rem_of`, `divides` and `div_exact` are provided, as
+        /// the other methods of the regular divisors, such as `mul_div` or the
+        /// multiples helpers, branch on their inputs. For non-secret values, use a
+        /// regular divisor built from [`get`](Self::get) instead.
+        #[derive(Clone, Copy)]
+        pub struct $SelfT {
+            d: $BaseT,
+            magic: $BaseT,
+            shift: u8,
+            sign_mask: $BaseT,
+            odd_shift: u8,
+            inverse: $BaseT,
+        }
+    };
+}
+
+macro_rules! constant_time_unsigned_impl {
+    ($SelfT:ident, $BaseT:ty) => {
+        /// Creates a constant-time divisor which can be used for computation of
+        /// division and modulo by `d`.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `d` equals zero.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(42);")]
+        /// ```
+        #[allow(clippy::cast_possible_truncation)]
+        pub const fn new(d: $BaseT) -> $SelfT {
+            // Forces a panic when d = 0, since we cannot use panic! in const.
+            let _ = 1 / d;
+
+            // Granlund and Montgomery, Figure 4.1: with l = ceil(log2(d)), the
+            // magic number is 1 + floor(2^N * (2^l - d) / d), which always
+            // fits into N bits.
+            let l = <$BaseT>::BITS - (d - 1).leading_zeros();
+            let pow: $BaseT = if l < <$BaseT>::BITS { 1 << l } else { 0 };
+            let (magic, _) = Self::div_rem_wide_by_base(pow.wrapping_sub(d), 0, d);
+
+            let (pre_shift, post_shift) = if l == 0 { (0, 0) } else { (1, l - 1) };
+
+            Self {
+                d,
+                magic: magic.wrapping_add(1),
+                pre_shift,
+                post_shift: post_shift as u8,
+                odd_shift: d.trailing_zeros() as u8,
+                inverse: Self::odd_inverse(d >> d.trailing_zeros()),
+            }
+        }
+
+        /// Returns the value that was used to construct this divisor as a primitive type.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(7);")]
+        /// assert_eq!(d.get(), 7);
+        /// ```
+        #[inline]
+        pub const fn get(&self) -> $BaseT {
+            self.d
+        }
+
+        /// Returns `true` if `n` is divisible by `self`.
+        ///
+        /// We take `0` to be divisible by all non-zero numbers.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(17);")]
+        /// assert!(d.divides(34));
+        /// ```
+        #[inline]
+        pub const fn divides(&self, n: $BaseT) -> bool {
+            self.rem_of(n) == 0
+        }
+
+        /// Returns the remainder of dividing `n` by `self`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(11);")]
+        /// let rem = d.rem_of(30);
+        /// assert_eq!(rem, 8);
+        /// ```
+        #[inline]
+        pub const fn rem_of(&self, n: $BaseT) -> $BaseT {
+            n - self.d * self.div_of(n)
+        }
+
+        /// Returns the result of dividing `n` by `self`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(17);")]
+        /// let div = d.div_of(34);
+        /// assert_eq!(div, 2);
+        /// ```
+        #[inline]
+        pub const fn div_of(&self, n: $BaseT) -> $BaseT {
+            let t = Self::mulh(self.magic, n);
+            (((n - t) >> self.pre_shift) + t) >> self.post_shift
+        }
+
+        /// Returns the result of dividing `n` by `self`, provided that `n` is a
+        /// multiple of `self`.
+        ///
+        /// This takes a single multiplication by the inverse of the odd part of
+        /// the divisor and a shift. If `n` is not a multiple of `self`, the result
+        /// is unspecified.
+        ///
+        /// # Panics
+        ///
+        /// Panics in debug builds if `n` is not divisible by `self`. This check is
+        /// not constant-time, and is omitted from release builds.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(12);")]
+        /// assert_eq!(d.div_exact(108), 9);
+        /// ```
+        #[inline]
+        #[allow(clippy::cast_lossless)]
+        pub const fn div_exact(&self, n: $BaseT) -> $BaseT {
+            // Forces a panic in debug builds when n is not a multiple of d,
+            // since we cannot use panic! in const.
+            #[cfg(debug_assertions)]
+            let _ = 1 / (self.divides(n) as $BaseT);
+
+            (n >> self.odd_shift).wrapping_mul(self.inverse)
+        }
+
+        odd_inverse_impl! { $BaseT }
+    };
+}
+
+macro_rules! constant_time_signed_impl {
+    ($SelfT:ident, $BaseT:ty, $UnsignedBaseT:ty) => {
+        /// Creates a constant-time divisor which can be used for computation of
+        /// division and modulo by `d`.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `d` equals zero.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(-21);")]
+        /// ```
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        pub const fn new(d: $BaseT) -> $SelfT {
+            // Forces a panic when d = 0, since we cannot use panic! in const.
+            let _ = 1 / d;
+
+            // Granlund and Montgomery, Figure 5.2: with l = max(ceil(log2(|d|)), 1),
+            // the magic number is 1 + floor(2^(N + l - 1) / |d|) - 2^N, which
+            // always fits into N bits.
+            let ud = d.unsigned_abs();
+            let mut l = <$UnsignedBaseT>::BITS - (ud - 1).leading_zeros();
+            if l == 0 {
+                l = 1;
+            }
+
+            let magic = if ud == 1 {
+                1
+            } else {
+                let (m, _) = Self::div_rem_wide_by_base(1 << (l - 1), 0, ud);
+                m.wrapping_add(1) as $BaseT
+            };
+
+            Self {
+                d,
+                magic,
+                shift: (l - 1) as u8,
+                sign_mask: d >> (<$BaseT>::BITS - 1),
+                odd_shift: d.trailing_zeros() as u8,
+                inverse: Self::odd_inverse(d >> d.trailing_zeros()),
+            }
+        }
+
+        /// Returns the value that was used to construct this divisor as a primitive type.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(-15);")]
+        /// assert_eq!(d.get(), -15);
+        /// ```
+        #[inline]
+        pub const fn get(&self) -> $BaseT {
+            self.d
+        }
+
+        /// Returns `true` if `n` is divisible by `self`.
+        ///
+        /// We take `0` to be divisible by all non-zero numbers.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(-9);")]
+        /// assert!(d.divides(27));
+        /// ```
+        #[inline]
+        pub const fn divides(&self, n: $BaseT) -> bool {
+            self.rem_of(n) == 0
+        }
+
+        /// Returns the remainder of dividing `n` by `self`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(21);")]
+        /// let rem = d.rem_of(-30);
+        /// assert_eq!(rem, -9);
+        /// ```
+        #[inline]
+        pub const fn rem_of(&self, n: $BaseT) -> $BaseT {
+            n.wrapping_sub(self.d.wrapping_mul(self.div_of(n)))
+        }
+
+        /// Returns the result of dividing `n` by `self`.
+        ///
+        /// This will perform a wrapping division, i.e.
+        #[doc = concat!("`", stringify!($SelfT), "::new(-1).div_of(", stringify!($BaseT) ,"::MIN)`")]
+        /// will always silently return
+        #[doc = concat!("`", stringify!($BaseT) ,"::MIN`")]
+        /// no matter whether the program was compiled with `overflow-checks` turned off or not.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(13);")]
+        /// let div = d.div_of(-30);
+        /// assert_eq!(div, -2);
+        /// ```
+        #[inline]
+        pub const fn div_of(&self, n: $BaseT) -> $BaseT {
+            let q = n.wrapping_add(Self::mulh(self.magic, n));
+            // Subtracting the sign of n rounds negative quotients towards zero.
+            let q = (q >> self.shift).wrapping_sub(n >> (<$BaseT>::BITS - 1));
+            // Conditionally negates q when the divisor is negative.
+            (q ^ self.sign_mask).wrapping_sub(self.sign_mask)
+        }
+
+        /// Returns the result of dividing `n` by `self`, provided that `n` is a
+        /// multiple of `self`.
+        ///
+        /// This takes a single multiplication by the inverse of the odd part of
+        /// the divisor and a shift. If `n` is not a multiple of `self`, the result
+        /// is unspecified.
+        ///
+        /// # Panics
+        ///
+        /// Panics in debug builds if `n` is not divisible by `self`. This check is
+        /// not constant-time, and is omitted from release builds.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(-12);")]
+        /// assert_eq!(d.div_exact(108), -9);
+        /// ```
+        #[inline]
+        #[allow(clippy::cast_lossless)]
+        pub const fn div_exact(&self, n: $BaseT) -> $BaseT {
+            // Forces a panic in debug builds when n is not a multiple of d,
+            // since we cannot use panic! in const.
+            #[cfg(debug_assertions)]
+            let _ = 1 / (self.divides(n) as $BaseT);
+
+            (n >> self.odd_shift).wrapping_mul(self.inverse)
+        }
+
+        odd_inverse_impl! { $BaseT }
+    };
+}
+
+macro_rules! constant_time_tests {
+    ($SelfT:ty, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(
+            clippy::cast_lossless,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            impl Arbitrary for $SelfT {
+                fn arbitrary(g: &mut Gen) -> $SelfT {
+                    let mut d = $BaseT::arbitrary(g);
+                    d = if d == 0 { 1 } else { d };
+
+                    <$SelfT>::new(d)
+                }
+            }
+
+            const EDGE_CASES: [$BaseT; 8] = [
+                $BaseT::MIN,
+                $BaseT::MIN + 1,
+                $BaseT::MAX,
+                $BaseT::MAX - 1,
+                $BaseT::MAX / 2,
+                $BaseT::MAX / 2 + 1,
+                1,
+                2,
+            ];
+
+            #[test]
+            #[should_panic(expected = "attempt to divide by zero")]
+            fn cannot_create_zero_divisors() {
+                let _ = <$SelfT>::new(0);
+            }
+
+            #[test]
+            fn agrees_with_builtin_on_edge_cases() {
+                for &d in EDGE_CASES.iter().filter(|&&d| d != 0) {
+                    let divisor = <$SelfT>::new(d);
+                    for &n in EDGE_CASES.iter() {
+                        assert_eq!(n / divisor, n.wrapping_div(d), "{} / {}", n, d);
+                        assert_eq!(n % divisor, n.wrapping_rem(d), "{} % {}", n, d);
+                    }
+                }
+            }
+
+            #[test]
+            fn agrees_with_builtin_exhaustively_for_small_widths() {
+                if <$BaseT>::BITS > 8 {
+                    return;
+                }
+
+                for d in ($BaseT::MIN..=$BaseT::MAX).filter(|&d| d != 0) {
+                    let divisor = <$SelfT>::new(d);
+                    for n in $BaseT::MIN..=$BaseT::MAX {
+                        assert_eq!(n / divisor, n.wrapping_div(d), "{} / {}", n, d);
+                        assert_eq!(n % divisor, n.wrapping_rem(d), "{} % {}", n, d);
+                    }
+                }
+            }
+
+            #[test]
+            fn agrees_with_builtin_near_zero() {
+                for d in (-64i16..=64).map(|d| d as $BaseT).filter(|&d| d != 0) {
+                    let divisor = <$SelfT>::new(d);
+                    for n in (-300i16..=300).map(|n| n as $BaseT) {
+                        assert_eq!(n / divisor, n.wrapping_div(d), "{} / {}", n, d);
+                        assert_eq!(n % divisor, n.wrapping_rem(d), "{} % {}", n, d);
+                    }
+                }
+            }
+
+            quickcheck! {
+                fn is_multiple_of_five(x: $BaseT) -> bool {
+                    let divisor = <$SelfT>::new(5);
+                    divisor.divides(x) == (x % 5 == 0)
+                }
+            }
+
+            quickcheck! {
+                fn div_agrees_with_builtin(x: $BaseT, d: $SelfT) -> bool {
+                    x / d == x.wrapping_div(d.get())
+                }
+            }
+
+            quickcheck! {
+                fn div_exact_agrees_with_div_of(x: $BaseT, d: $SelfT) -> bool {
+                    let n = x.wrapping_sub(x.wrapping_rem(d.get()));
+                    d.div_exact(n) == d.div_of(n)
+                }
+            }
+
+            #[test]
+            #[cfg(debug_assertions)]
+            #[should_panic(expected = "attempt to divide by zero")]
+            fn div_exact_rejects_non_multiples() {
+                let _ = <$SelfT>::new(6).div_exact(9);
+            }
+
+            #[test]
+            fn div_exact_in_const_context() {
+                const QUOTIENT: $BaseT = <$SelfT>::new(12).div_exact(108);
+                assert_eq!(QUOTIENT, 9);
+            }
+
+            quickcheck! {
+                fn rem_agrees_with_builtin(x: $BaseT, d: $SelfT) -> bool {
+                    x % d == x.wrapping_rem(d.get())
+                }
+            }
+
+            quickcheck! {
+                fn div_by_power_of_two_agrees_with_builtin(x: $BaseT, k: u8) -> bool {
+                    let d: $BaseT = 1 << (u32::from(k) % (<$BaseT>::BITS - 1));
+                    let divisor = <$SelfT>::new(d);
+                    x / divisor == x.wrapping_div(d)
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,239 @@
+use crate::DivisorU64;
+
+/// Error returned when a [`Crt`] cannot be built from the given moduli.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CrtError {
+    /// The modulus at the given index shares a common factor with one of the
+    /// moduli before it.
+    NotCoprime(usize),
+    /// The product of all the moduli does not fit into a `u128`.
+    Overflow,
+}
+
+impl core::fmt::Display for CrtError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CrtError::NotCoprime(index) => {
+                write!(
+                    f,
+                    "modulus at index {} is not coprime to the moduli before it",
+                    index
+                )
+            }
+            CrtError::Overflow => write!(f, "product of the moduli does not fit into a u128"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CrtError {}
+
+/// Reconstructs values from their residues modulo `N` pairwise coprime moduli,
+/// using the Chinese Remainder Theorem.
+///
+/// All coefficients are precomputed when the combiner is built, after which
+/// residues are combined using Garner's algorithm, with every reduction done
+/// by the precomputed divisors. The product of the moduli must fit into a
+/// `u128`, and the combined value is the unique one below that product.
+///
+/// # Examples
+/// ```
+/// use quickdiv::{Crt, CrtError, DivisorU64};
+///
+/// let crt = Crt::new([DivisorU64::new(3), DivisorU64::new(5), DivisorU64::new(7)]).unwrap();
+/// assert_eq!(crt.product(), 105);
+/// assert_eq!(crt.combine(&[2, 3, 2]), 23);
+///
+/// let err = Crt::new([DivisorU64::new(6), DivisorU64::new(4)]).unwrap_err();
+/// assert_eq!(err, CrtError::NotCoprime(1));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Crt<const N: usize> {
+    moduli: [DivisorU64; N],
+    // The product of all moduli before each one.
+    prefix_products: [u128; N],
+    // The inverse of each prefix product modulo the corresponding modulus.
+    inverses: [u64; N],
+    product: u128,
+}
+
+impl<const N: usize> Crt<N> {
+    /// Creates a combiner for residues modulo `moduli`, precomputing all the
+    /// coefficients needed by [`combine`](Self::combine).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CrtError::NotCoprime`] if the moduli are not pairwise coprime,
+    /// and [`CrtError::Overflow`] if their product does not fit into a `u128`.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickdiv::{Crt, CrtError, DivisorU64};
+    ///
+    /// let max = DivisorU64::new(u64::MAX);
+    /// assert!(Crt::new([max, DivisorU64::new(u64::MAX - 1)]).is_ok());
+    /// assert_eq!(Crt::new([max, max, DivisorU64::new(2)]).unwrap_err(), CrtError::NotCoprime(1));
+    /// ```
+    pub fn new(moduli: [DivisorU64; N]) -> Result<Self, CrtError> {
+        let mut prefix_products = [0; N];
+        let mut inverses = [0; N];
+        let mut product: u128 = 1;
+
+        for (i, m) in moduli.iter().enumerate() {
+            let reduced = Self::reduce(m, product);
+            inverses[i] = m.inverse_of(reduced).ok_or(CrtError::NotCoprime(i))?;
+            prefix_products[i] = product;
+            product = product
+                .checked_mul(u128::from(m.get()))
+                .ok_or(CrtError::Overflow)?;
+        }
+
+        Ok(Self {
+            moduli,
+            prefix_products,
+            inverses,
+            product,
+        })
+    }
+
+    /// Returns the moduli used by this combiner.
+    pub fn moduli(&self) -> &[DivisorU64; N] {
+        &self.moduli
+    }
+
+    /// Returns the product of all the moduli.
+    pub fn product(&self) -> u128 {
+        self.product
+    }
+
+    /// Returns the unique value below [`product`](Self::product) which is
+    /// congruent to `residues[i]` modulo the `i`-th modulus, for every `i`.
+    ///
+    /// Residues need not be reduced.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickdiv::{Crt, DivisorU64};
+    ///
+    /// let crt = Crt::new([DivisorU64::new(u64::MAX), DivisorU64::new(1 << 32)]).unwrap();
+    /// let x = u128::MAX / 3;
+    /// let residues = [(x % u64::MAX as u128) as u64, x as u64];
+    /// assert_eq!(crt.combine(&residues), x % crt.product());
+    /// ```
+    ///
+    /// Passing the wrong number of residues does not compile:
+    ///
+    /// ```compile_fail
+    /// use quickdiv::{Crt, DivisorU64};
+    ///
+    /// let crt = Crt::new([DivisorU64::new(3), DivisorU64::new(5)]).unwrap();
+    /// let _ = crt.combine(&[1]);
+    /// ```
+    pub fn combine(&self, residues: &[u64; N]) -> u128 {
+        // Garner's algorithm: the value is built up in mixed radix, with each
+        // step fixing one more residue without disturbing the earlier ones.
+        let mut x: u128 = 0;
+        for (i, (modulus, &residue)) in self.moduli.iter().zip(residues).enumerate() {
+            let residue = modulus.rem_of(residue);
+            let current = Self::reduce(modulus, x);
+
+            let diff = if residue >= current {
+                residue - current
+            } else {
+                residue.wrapping_sub(current).wrapping_add(modulus.get())
+            };
+            let digit = modulus.mul_rem(diff, self.inverses[i]);
+            x += u128::from(digit) * self.prefix_products[i];
+        }
+
+        x
+    }
+
+    /// Same as [`combine`](Self::combine), but returns the result as a pair of
+    /// its top and bottom 64 bits.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickdiv::{Crt, DivisorU64};
+    ///
+    /// let crt = Crt::new([DivisorU64::new(u64::MAX), DivisorU64::new(1 << 32)]).unwrap();
+    /// assert_eq!(crt.combine_wide(&[0, 1 << 31]), ((1 << 31) - 1, u64::MAX - (1 << 31) + 1));
+    /// ```
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn combine_wide(&self, residues: &[u64; N]) -> (u64, u64) {
+        let x = self.combine(residues);
+        ((x >> 64) as u64, x as u64)
+    }
+
+    // Reduces a u128 modulo m, first reducing its top half so that the quotient
+    // fits into a single word.
+    #[allow(clippy::cast_possible_truncation)]
+    fn reduce(m: &DivisorU64, x: u128) -> u64 {
+        let hi = m.rem_of((x >> 64) as u64);
+        m.div_rem_wide(hi, x as u64).1
+    }
+}
+
+macro_rules! crt_tests {
+    ($ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_lossless, clippy::cast_possible_truncation)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            #[test]
+            fn rejects_common_factors() {
+                let moduli = [DivisorU64::new(15), DivisorU64::new(7), DivisorU64::new(21)];
+                assert_eq!(Crt::new(moduli).unwrap_err(), CrtError::NotCoprime(2));
+            }
+
+            #[test]
+            fn rejects_overflowing_products() {
+                let moduli = [
+                    DivisorU64::new(u64::MAX),
+                    DivisorU64::new(u64::MAX - 1),
+                    DivisorU64::new(u64::MAX - 2),
+                ];
+                assert_eq!(Crt::new(moduli).unwrap_err(), CrtError::Overflow);
+            }
+
+            #[test]
+            fn handles_no_moduli() {
+                let crt = Crt::new([]).unwrap();
+                assert_eq!((crt.product(), crt.combine(&[])), (1, 0));
+            }
+
+            quickcheck! {
+                fn recovers_value_from_residues(x: u128, a: u64, b: u64) -> TestResult {
+                    let (a, b) = (a | 1, b.wrapping_shl(b as u32 % 64).max(1));
+                    let crt = match Crt::new([DivisorU64::new(a), DivisorU64::new(b)]) {
+                        Ok(crt) => crt,
+                        Err(_) => return TestResult::discard(),
+                    };
+
+                    let x = x % crt.product();
+                    let residues = [(x % a as u128) as u64, (x % b as u128) as u64];
+                    let (hi, lo) = crt.combine_wide(&residues);
+                    TestResult::from_bool(crt.combine(&residues) == x && ((hi as u128) << 64 | lo as u128) == x)
+                }
+            }
+
+            quickcheck! {
+                fn combines_many_small_moduli(x: u64) -> bool {
+                    let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+                    let mut moduli = [DivisorU64::new(1); 15];
+                    let mut residues = [0; 15];
+                    for i in 0..15 {
+                        moduli[i] = DivisorU64::new(primes[i]);
+                        residues[i] = x % primes[i];
+                    }
+
+                    let crt = Crt::new(moduli).unwrap();
+                    crt.combine(&residues) == x as u128 % crt.product()
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,167 @@
+macro_rules! unsigned_gcd_impl {
+    ($SelfT:ident, $BaseT:ty, $SignedT:ty) => {
+        /// Returns the greatest common divisor of `self` and `x`.
+        ///
+        /// The first step of the Euclidean algorithm, reducing `x` modulo
+        /// `self`, uses the precomputed divisor, and the rest is computed with
+        /// the binary GCD algorithm, which needs no further divisions.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(84);")]
+        /// assert_eq!(d.gcd_with(120), 12);
+        /// assert_eq!(d.gcd_with(0), 84);
+        /// ```
+        pub fn gcd_with(&self, x: $BaseT) -> $BaseT {
+            let mut a = self.get();
+            let mut b = self.rem_of(x);
+            if b == 0 {
+                return a;
+            }
+
+            let shift = (a | b).trailing_zeros();
+            a >>= a.trailing_zeros();
+            loop {
+                b >>= b.trailing_zeros();
+                if a > b {
+                    core::mem::swap(&mut a, &mut b);
+                }
+                b -= a;
+                if b == 0 {
+                    return a << shift;
+                }
+            }
+        }
+
+        /// Returns the greatest common divisor `g` of `self` and `x`, along
+        /// with Bézout coefficients `s` and `t` such that `g = s * self + t * x`.
+        ///
+        /// The coefficients are the minimal ones produced by the extended
+        /// Euclidean algorithm, which always fit into
+        #[doc = concat!("`", stringify!($SignedT), "`.")]
+        /// The first step of the algorithm uses the precomputed divisor.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(12);")]
+        /// let (g, s, t) = d.extended_gcd_with(42);
+        /// assert_eq!(g, 6);
+        /// assert_eq!(s * 12 + t * 42, 6);
+        /// ```
+        #[allow(clippy::cast_possible_wrap)]
+        pub fn extended_gcd_with(&self, x: $BaseT) -> ($BaseT, $SignedT, $SignedT) {
+            let q = self.div_of(x);
+            let (mut r0, mut s0, mut t0): ($BaseT, $SignedT, $SignedT) = (self.get(), 1, 0);
+            let (mut r1, mut s1, mut t1) = (self.rem_of(x), (q as $SignedT).wrapping_neg(), 1);
+
+            // Only the coefficients of the final row are guaranteed to fit, so
+            // the discarded ones are allowed to wrap around.
+            while r1 != 0 {
+                let q = r0 / r1;
+                let r2 = r0 - q * r1;
+                let s2 = s0.wrapping_sub((q as $SignedT).wrapping_mul(s1));
+                let t2 = t0.wrapping_sub((q as $SignedT).wrapping_mul(t1));
+
+                r0 = r1;
+                s0 = s1;
+                t0 = t1;
+                r1 = r2;
+                s1 = s2;
+                t1 = t2;
+            }
+
+            (r0, s0, t0)
+        }
+
+        /// Returns the inverse of `x` modulo `self`, or `None` if `x` and `self`
+        /// are not coprime.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(97);")]
+        /// assert_eq!(d.inverse_of(10), Some(68));
+        /// assert_eq!(d.inverse_of(97), None);
+        /// ```
+        #[allow(clippy::cast_sign_loss)]
+        pub fn inverse_of(&self, x: $BaseT) -> Option<$BaseT> {
+            let (g, _, t) = self.extended_gcd_with(x);
+            if g != 1 {
+                return None;
+            }
+
+            if t < 0 {
+                Some((t as $BaseT).wrapping_add(self.get()))
+            } else {
+                Some(t as $BaseT)
+            }
+        }
+    };
+}
+
+macro_rules! gcd_tests {
+    ($SelfT:ident, $BaseT:ident, $SignedT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_sign_loss)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            fn reference_gcd(mut a: $BaseT, mut b: $BaseT) -> $BaseT {
+                while b != 0 {
+                    let r = a % b;
+                    a = b;
+                    b = r;
+                }
+                a
+            }
+
+            #[test]
+            fn handles_extreme_values() {
+                let d = $SelfT::new(1);
+                assert_eq!(d.gcd_with(<$BaseT>::MAX), 1);
+                assert_eq!(d.extended_gcd_with(<$BaseT>::MAX), (1, 1, 0));
+                assert_eq!(d.inverse_of(<$BaseT>::MAX), Some(0));
+
+                let d = $SelfT::new(<$BaseT>::MAX);
+                assert_eq!(d.gcd_with(<$BaseT>::MAX - 1), 1);
+                assert_eq!(d.inverse_of(<$BaseT>::MAX - 1), Some(<$BaseT>::MAX - 1));
+                assert_eq!(d.extended_gcd_with(0), (<$BaseT>::MAX, 1, 0));
+            }
+
+            quickcheck! {
+                fn gcd_agrees_with_euclid(d: $SelfT, x: $BaseT) -> bool {
+                    d.gcd_with(x) == reference_gcd(d.get(), x)
+                }
+            }
+
+            quickcheck! {
+                fn extended_gcd_returns_bezout_coefficients(d: $SelfT, x: $BaseT) -> bool {
+                    let (g, s, t) = d.extended_gcd_with(x);
+                    let combination = (s as $BaseT).wrapping_mul(d.get()).wrapping_add((t as $BaseT).wrapping_mul(x));
+
+                    // Minimal coefficients are bounded by the other operand divided by g.
+                    g == reference_gcd(d.get(), x)
+                        && combination == g
+                        && (x == 0 || s.unsigned_abs() <= x / g)
+                        && t.unsigned_abs() <= d.get() / g
+                }
+            }
+
+            quickcheck! {
+                fn inverse_is_inverse(d: $SelfT, x: $BaseT) -> bool {
+                    match d.inverse_of(x) {
+                        Some(y) => y < d.get() && d.mul_rem(x, y) == d.rem_of(1),
+                        None => d.gcd_with(x) != 1,
+                    }
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,141 @@
+macro_rules! horner_impl {
+    ($SelfT:ident, $BaseT:ty) => {
+        /// Returns the remainder of dividing by `self` the number whose digits
+        /// in radix `base` are given by `digits`, most significant digit first.
+        ///
+        /// This evaluates `acc * base + digit` modulo `self` for each digit
+        /// using the precomputed reciprocal of the divisor, so neither the
+        /// number nor any intermediate value needs to fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        /// Digits are not required to be less than `base`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        /// // ISBN-10 0-306-40615-2 is valid, as its weighted digit sum is a
+        /// // multiple of 11.
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(11);")]
+        /// let isbn = [0, 3, 0, 6, 4, 0, 6, 1, 5, 2];
+        /// let weighted = isbn.iter().zip((1..=10).rev()).map(|(&digit, weight)| digit * weight);
+        /// assert_eq!(d.rem_of_digits(weighted, 1), 0);
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(7);")]
+        /// assert_eq!(d.rem_of_digits([1, 2, 3], 10), 123 % 7);
+        /// ```
+        pub fn rem_of_digits<I: IntoIterator<Item = $BaseT>>(
+            &self,
+            digits: I,
+            base: $BaseT,
+        ) -> $BaseT {
+            digits.into_iter().fold(0, |acc, digit| {
+                let (lo, carry) = acc.wrapping_mul(base).overflowing_add(digit);
+                let hi = Self::mulh(acc, base) + carry as $BaseT;
+                self.horner_step(hi, lo)
+            })
+        }
+
+        /// Returns the remainder of dividing by `self` the big-endian unsigned
+        /// integer stored in `bytes`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(97);")]
+        /// // 0x123456 = 1193046 = 97 * 12299 + 43
+        /// assert_eq!(d.rem_of_bytes_be(&[0x12, 0x34, 0x56]), 43);
+        /// assert_eq!(d.rem_of_bytes_be(&[]), 0);
+        /// ```
+        pub fn rem_of_bytes_be(&self, bytes: &[u8]) -> $BaseT {
+            bytes.iter().fold(0, |acc, &byte| {
+                // `acc * 256 + byte` as a double-width value. The left shift
+                // is split in two so that it clears `acc` for 8-bit types
+                // instead of overflowing.
+                let hi = acc >> (<$BaseT>::BITS - 8);
+                let lo = ((acc << 4) << 4) | byte as $BaseT;
+                self.horner_step(hi, lo)
+            })
+        }
+
+        // Reduces hi * 2^N + lo, which is less than the divisor times 2^N
+        // whenever the accumulator is a remainder and the radix fits into
+        // N bits, so the division is a valid 2-by-1 division.
+        #[inline]
+        fn horner_step(&self, hi: $BaseT, lo: $BaseT) -> $BaseT {
+            Self::div_rem_wide_preinv(hi, lo, self.get(), self.reciprocal).1
+        }
+    };
+}
+
+macro_rules! horner_tests {
+    ($DivisorT:ident, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_lossless, clippy::cast_possible_truncation)]
+        mod $ModName {
+            use quickcheck::*;
+            use std::vec::Vec;
+
+            use super::*;
+
+            reference_wide_impl! { $BaseT }
+
+            fn reference_rem_of_digits(digits: &[$BaseT], base: $BaseT, d: $BaseT) -> $BaseT {
+                digits.iter().fold(0, |acc, &digit| {
+                    let (hi, lo) = reference_mul_wide(acc, base);
+                    let (lo, carry) = lo.overflowing_add(digit);
+                    reference_div_rem_wide(hi + carry as $BaseT, lo, d).1
+                })
+            }
+
+            // Moves the country code and check digits to the end, replaces
+            // letters by two-digit numbers starting from 10, and checks that
+            // the result is congruent to 1 modulo 97.
+            fn iban_mod97(iban: &str) -> bool {
+                let d = $DivisorT::new(97);
+                let (head, tail) = iban.split_at(4);
+                let digits = tail.chars().chain(head.chars()).flat_map(|c| {
+                    let value = c.to_digit(36).unwrap() as $BaseT;
+                    if value < 10 {
+                        Some(value).into_iter().chain(None)
+                    } else {
+                        Some(value / 10).into_iter().chain(Some(value % 10))
+                    }
+                });
+                d.rem_of_digits(digits, 10) == 1
+            }
+
+            #[test]
+            fn validates_ibans() {
+                assert!(iban_mod97("GB82WEST12345698765432"));
+                assert!(iban_mod97("DE89370400440532013000"));
+                assert!(iban_mod97("NL91ABNA0417164300"));
+                assert!(!iban_mod97("GB82WEST12345698765423"));
+                assert!(!iban_mod97("DE89370400440532013001"));
+            }
+
+            #[test]
+            fn handles_max_values() {
+                let d = $DivisorT::new(<$BaseT>::MAX);
+                let digits = [<$BaseT>::MAX - 1, <$BaseT>::MAX, <$BaseT>::MAX - 1];
+                assert_eq!(d.rem_of_digits(digits, <$BaseT>::MAX), <$BaseT>::MAX - 1);
+                assert_eq!(d.rem_of_bytes_be(&[0xff; 64]), 0);
+                assert_eq!(d.rem_of_digits(None, 10), 0);
+            }
+
+            quickcheck! {
+                fn rem_of_digits_agrees_with_reference(digits: (Vec<$BaseT>, $BaseT), d: $DivisorT) -> bool {
+                    let (digits, base) = digits;
+                    d.rem_of_digits(digits.iter().copied(), base) == reference_rem_of_digits(&digits, base, d.get())
+                }
+            }
+
+            quickcheck! {
+                fn rem_of_bytes_be_agrees_with_nibbles(bytes: Vec<u8>, d: $DivisorT) -> bool {
+                    let nibbles = bytes.iter().flat_map(|&b| [(b >> 4) as $BaseT, (b & 0xf) as $BaseT]);
+                    d.rem_of_bytes_be(&bytes) == reference_rem_of_digits(&nibbles.collect::<Vec<_>>(), 16, d.get())
+                }
+            }
+        }
+    };
+}
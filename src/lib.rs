@@ -54,65 +54,358 @@ struct ReadmeDoctest {}
 
 extern crate core;
 
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
+#[cfg(feature = "num-bigint")]
+extern crate alloc;
+
 #[macro_use] // import impl_traits!
 mod traits;
+#[macro_use] // import declare_cache_struct!, cache_tests!
+mod cache;
+#[macro_use] // import declare_const_struct!, const_impl!, const_*_impl!, const_divisor_tests!
+mod const_divisor;
+#[macro_use] // import declare_constant_time_*_struct!, constant_time_*_impl!, constant_time_tests!
+mod constant_time;
+#[cfg(feature = "num-bigint")]
+mod bigint;
+#[macro_use] // import declare_binner_struct!, binner_tests!
+mod binner;
+#[macro_use] // import crt_tests!
+mod crt;
+#[macro_use] // import unsigned_gcd_impl!, gcd_tests!
+mod gcd;
+#[macro_use] // import horner_impl!, horner_tests!
+mod horner;
+#[macro_use] // import limbs_impl!, limbs_tests!
+mod limbs;
+#[macro_use] // import declare_mixed_radix_struct!, mixed_radix_tests!
+mod mixed_radix;
+#[macro_use] // import declare_modulus_struct!, modulus_tests!
+mod modulus;
+#[macro_use] // import declare_montgomery_struct!, montgomery_tests!
+mod montgomery;
+#[macro_use] // import *_mul_div_impl!, *_mul_div_tests!
+mod mul_div;
+#[macro_use] // import declare_multiples_struct!, *_multiples_impl!, *_multiples_tests!
+mod multiples;
+#[macro_use] // import declare_adaptive_struct!, policy_impl!, policy_tests!
+mod policy;
+#[macro_use] // import prime_buckets_tests!
+mod prime_buckets;
+#[macro_use] // import declare_digits_struct!, radix_impl!, radix_tests!
+mod radix;
+#[macro_use] // import declare_range_reducer_struct!, range_reducer_tests!
+mod range_reducer;
+#[macro_use] // import rolling_hash_tests!
+mod rolling_hash;
+#[macro_use] // import sample_impl!, sample_tests!
+mod sample;
 #[macro_use] // import declare_signed_structs!, signed_impl!
 mod signed;
-#[macro_use] // import tests!
+#[macro_use] // import slice_impl!
+mod slice;
+#[macro_use] // import declare_even_chunks_struct!, split_impl!, split_tests!
+mod split;
+#[macro_use] // import tests!, reference_wide_impl!, signed_tests!
 mod tests;
-#[macro_use] // import declare_unsigned_structs!, unsigned_impl!
+#[macro_use] // import declare_unsigned_structs!, unsigned_impl!, div_rem_wide_tests!
 mod unsigned;
-#[macro_use] // import widen_mulh_impl!, mulh_impl!, widen_div_rem_impl!, divlu_impl!
+#[macro_use] // import widen_mulh_impl!, mulh_impl!, widen_div_rem_impl!, divlu_impl!, odd_inverse_impl!, div_exact_tests!
 mod utils;
 
+pub mod wide;
+
+pub use crt::{Crt, CrtError};
+pub use mul_div::RoundingMode;
+#[cfg(feature = "std")]
+pub use policy::calibrate;
+pub use policy::DivisionPolicy;
+pub use prime_buckets::PrimeBucketIndexer;
+pub use rolling_hash::RollingHash;
+
 // DivisorU8
 
 declare_unsigned_structs! { DivisorU8, InnerDivisorU8, u8, "8-bit" }
 
 impl DivisorU8 {
     unsigned_impl! { DivisorU8, InnerDivisorU8, u8 }
+    slice_impl! { DivisorU8, u8 }
+    unsigned_mul_div_impl! { DivisorU8, u8 }
+    unsigned_gcd_impl! { DivisorU8, u8, i8 }
+    radix_impl! { DivisorU8, DigitsU8, u8 }
+    unsigned_multiples_impl! { DivisorU8, MultiplesU8, u8 }
+    split_impl! { DivisorU8, EvenChunksU8, u8 }
+    horner_impl! { DivisorU8, u8 }
+    sample_impl! { DivisorU8, u8 }
     widen_mulh_impl! { u8, u16 }
     widen_div_rem_impl! { u8, u16 }
 }
 
 impl_traits! { DivisorU8, u8 }
 
+declare_cache_struct! { DivisorCacheU8, DivisorU8, u8, u8 }
+
+declare_const_struct! { ConstDivisorU8, DivisorU8, u8, "8-bit" }
+
+impl<const D: u8> ConstDivisorU8<D> {
+    const_unsigned_impl! { ConstDivisorU8, u8 }
+    const_forward_impl! { ConstDivisorU8, DivisorU8, MultiplesU8, u8, u8, u8 }
+}
+
+declare_adaptive_struct! { AdaptiveDivisorU8, InnerAdaptiveDivisorU8, DivisorU8, u8, "8-bit" }
+
+impl AdaptiveDivisorU8 {
+    unsigned_multiples_impl! { AdaptiveDivisorU8, MultiplesU8, u8 }
+}
+
+declare_digits_struct! { DigitsU8, DivisorU8, u8 }
+
+declare_mixed_radix_struct! { MixedRadixU8, DivisorU8, u8 }
+
+declare_multiples_struct! { MultiplesU8, DivisorU8, u8, u8 }
+
+declare_binner_struct! { BinnerU8, DivisorU8, u8, u8 }
+
+declare_even_chunks_struct! { EvenChunksU8, DivisorU8, u8 }
+
 tests! { DivisorU8, u8 }
 
+div_exact_tests! { DivisorU8, u8, div_exact_u8 }
+
+unsigned_mul_div_tests! { DivisorU8, u8, mul_div_u8 }
+
+div_rem_wide_tests! { DivisorU8, u8, div_rem_wide_u8 }
+
+gcd_tests! { DivisorU8, u8, i8, gcd_u8 }
+
+radix_tests! { DivisorU8, u8, radix_u8 }
+
+mixed_radix_tests! { MixedRadixU8, DivisorU8, u8, mixed_radix_u8 }
+
+unsigned_multiples_tests! { DivisorU8, u8, multiples_u8 }
+
+split_tests! { DivisorU8, u8, split_u8 }
+
+horner_tests! { DivisorU8, u8, horner_u8 }
+
+sample_tests! { DivisorU8, u8, sample_u8 }
+
+binner_tests! { BinnerU8, u8, u8, binner_u8 }
+
+cache_tests! { DivisorCacheU8, u8, cache_u8 }
+
+const_divisor_tests! { ConstDivisorU8, DivisorU8, u8, const_u8 }
+
+policy_tests! { AdaptiveDivisorU8, u8, policy_u8 }
+
 // DivisorU16
 
 declare_unsigned_structs! { DivisorU16, InnerDivisorU16, u16, "16-bit" }
 
 impl DivisorU16 {
     unsigned_impl! { DivisorU16, InnerDivisorU16, u16 }
+    slice_impl! { DivisorU16, u16 }
+    unsigned_mul_div_impl! { DivisorU16, u16 }
+    unsigned_gcd_impl! { DivisorU16, u16, i16 }
+    radix_impl! { DivisorU16, DigitsU16, u16 }
+    unsigned_multiples_impl! { DivisorU16, MultiplesU16, u16 }
+    split_impl! { DivisorU16, EvenChunksU16, u16 }
+    horner_impl! { DivisorU16, u16 }
+    sample_impl! { DivisorU16, u16 }
     widen_mulh_impl! { u16, u32 }
     widen_div_rem_impl! { u16, u32 }
 }
 
 impl_traits! { DivisorU16, u16 }
 
+declare_cache_struct! { DivisorCacheU16, DivisorU16, u16, u16 }
+
+declare_const_struct! { ConstDivisorU16, DivisorU16, u16, "16-bit" }
+
+impl<const D: u16> ConstDivisorU16<D> {
+    const_unsigned_impl! { ConstDivisorU16, u16 }
+    const_forward_impl! { ConstDivisorU16, DivisorU16, MultiplesU16, u16, u16, u16 }
+}
+
+declare_adaptive_struct! { AdaptiveDivisorU16, InnerAdaptiveDivisorU16, DivisorU16, u16, "16-bit" }
+
+impl AdaptiveDivisorU16 {
+    unsigned_multiples_impl! { AdaptiveDivisorU16, MultiplesU16, u16 }
+}
+
+declare_digits_struct! { DigitsU16, DivisorU16, u16 }
+
+declare_mixed_radix_struct! { MixedRadixU16, DivisorU16, u16 }
+
+declare_multiples_struct! { MultiplesU16, DivisorU16, u16, u16 }
+
+declare_binner_struct! { BinnerU16, DivisorU16, u16, u16 }
+
+declare_even_chunks_struct! { EvenChunksU16, DivisorU16, u16 }
+
 tests! { DivisorU16, u16 }
 
+div_exact_tests! { DivisorU16, u16, div_exact_u16 }
+
+unsigned_mul_div_tests! { DivisorU16, u16, mul_div_u16 }
+
+div_rem_wide_tests! { DivisorU16, u16, div_rem_wide_u16 }
+
+gcd_tests! { DivisorU16, u16, i16, gcd_u16 }
+
+radix_tests! { DivisorU16, u16, radix_u16 }
+
+mixed_radix_tests! { MixedRadixU16, DivisorU16, u16, mixed_radix_u16 }
+
+unsigned_multiples_tests! { DivisorU16, u16, multiples_u16 }
+
+split_tests! { DivisorU16, u16, split_u16 }
+
+horner_tests! { DivisorU16, u16, horner_u16 }
+
+sample_tests! { DivisorU16, u16, sample_u16 }
+
+binner_tests! { BinnerU16, u16, u16, binner_u16 }
+
+cache_tests! { DivisorCacheU16, u16, cache_u16 }
+
+const_divisor_tests! { ConstDivisorU16, DivisorU16, u16, const_u16 }
+
+policy_tests! { AdaptiveDivisorU16, u16, policy_u16 }
+
 // DivisorU32
 
-declare_unsigned_structs! { DivisorU32, InnerDivisorU32, u32, "32-bit" }
+declare_unsigned_structs! {
+    ///
+    /// # Big integers
+    ///
+    /// With the optional `num-bigint` feature, this divisor can also divide
+    /// `BigUint` and `BigInt` values. Unlike the `Rem` implementations of
+    /// `num-bigint` itself, which return a big integer, `%` by this divisor
+    /// returns the remainder as a primitive, since it always fits: a `u32` for
+    /// `BigUint` and an `i64` with the sign of the dividend for `BigInt`.
+    DivisorU32,
+    InnerDivisorU32,
+    u32,
+    "32-bit"
+}
 
 impl DivisorU32 {
     unsigned_impl! { DivisorU32, InnerDivisorU32, u32 }
+    slice_impl! { DivisorU32, u32 }
+    unsigned_mul_div_impl! { DivisorU32, u32 }
+    unsigned_gcd_impl! { DivisorU32, u32, i32 }
+    radix_impl! { DivisorU32, DigitsU32, u32 }
+    unsigned_multiples_impl! { DivisorU32, MultiplesU32, u32 }
+    split_impl! { DivisorU32, EvenChunksU32, u32 }
+    horner_impl! { DivisorU32, u32 }
+    sample_impl! { DivisorU32, u32 }
+    limbs_impl! { DivisorU32, u32 }
     widen_mulh_impl! { u32, u64 }
     widen_div_rem_impl! { u32, u64 }
 }
 
 impl_traits! { DivisorU32, u32 }
 
+declare_cache_struct! { DivisorCacheU32, DivisorU32, u32, u32 }
+
+declare_const_struct! { ConstDivisorU32, DivisorU32, u32, "32-bit" }
+
+impl<const D: u32> ConstDivisorU32<D> {
+    const_unsigned_impl! { ConstDivisorU32, u32 }
+    const_forward_impl! { ConstDivisorU32, DivisorU32, MultiplesU32, u32, u32, u32 }
+}
+
+declare_adaptive_struct! { AdaptiveDivisorU32, InnerAdaptiveDivisorU32, DivisorU32, u32, "32-bit" }
+
+impl AdaptiveDivisorU32 {
+    unsigned_multiples_impl! { AdaptiveDivisorU32, MultiplesU32, u32 }
+}
+
+declare_digits_struct! { DigitsU32, DivisorU32, u32 }
+
+declare_mixed_radix_struct! { MixedRadixU32, DivisorU32, u32 }
+
+declare_multiples_struct! { MultiplesU32, DivisorU32, u32, u32 }
+
+declare_binner_struct! { BinnerU32, DivisorU32, u32, u32 }
+
+declare_even_chunks_struct! { EvenChunksU32, DivisorU32, u32 }
+
+declare_modulus_struct! { ModulusU32, DivisorU32, u32, "32-bit" }
+
+declare_montgomery_struct! { MontgomeryU32, DivisorU32, u32, "32-bit" }
+
+declare_range_reducer_struct! { RangeReducerU32, DivisorU32, u32, "32-bit" }
+
 tests! { DivisorU32, u32 }
 
+div_exact_tests! { DivisorU32, u32, div_exact_u32 }
+
+unsigned_mul_div_tests! { DivisorU32, u32, mul_div_u32 }
+
+div_rem_wide_tests! { DivisorU32, u32, div_rem_wide_u32 }
+
+gcd_tests! { DivisorU32, u32, i32, gcd_u32 }
+
+radix_tests! { DivisorU32, u32, radix_u32 }
+
+mixed_radix_tests! { MixedRadixU32, DivisorU32, u32, mixed_radix_u32 }
+
+unsigned_multiples_tests! { DivisorU32, u32, multiples_u32 }
+
+split_tests! { DivisorU32, u32, split_u32 }
+
+horner_tests! { DivisorU32, u32, horner_u32 }
+
+sample_tests! { DivisorU32, u32, sample_u32 }
+
+binner_tests! { BinnerU32, u32, u32, binner_u32 }
+
+range_reducer_tests! { RangeReducerU32, u32, u64, range_reducer_u32 }
+
+limbs_tests! { DivisorU32, u32, u64, limbs_u32 }
+
+cache_tests! { DivisorCacheU32, u32, cache_u32 }
+
+const_divisor_tests! { ConstDivisorU32, DivisorU32, u32, const_u32 }
+
+policy_tests! { AdaptiveDivisorU32, u32, policy_u32 }
+
+modulus_tests! { ModulusU32, u32, modulus_u32 }
+
+montgomery_tests! { MontgomeryU32, ModulusU32, u32, montgomery_u32 }
+
 // DivisorU64
 
-declare_unsigned_structs! { DivisorU64, InnerDivisorU64, u64, "64-bit" }
+declare_unsigned_structs! {
+    ///
+    /// # Big integers
+    ///
+    /// With the optional `num-bigint` feature, this divisor can also divide
+    /// `BigUint` and `BigInt` values. Unlike the `Rem` implementations of
+    /// `num-bigint` itself, which return a big integer, `%` by this divisor
+    /// returns the remainder as a primitive, since it always fits: a `u64` for
+    /// `BigUint` and an `i128` with the sign of the dividend for `BigInt`.
+    DivisorU64,
+    InnerDivisorU64,
+    u64,
+    "64-bit"
+}
 
 impl DivisorU64 {
     unsigned_impl! { DivisorU64, InnerDivisorU64, u64 }
+    slice_impl! { DivisorU64, u64 }
+    unsigned_mul_div_impl! { DivisorU64, u64 }
+    unsigned_gcd_impl! { DivisorU64, u64, i64 }
+    radix_impl! { DivisorU64, DigitsU64, u64 }
+    unsigned_multiples_impl! { DivisorU64, MultiplesU64, u64 }
+    split_impl! { DivisorU64, EvenChunksU64, u64 }
+    horner_impl! { DivisorU64, u64 }
+    sample_impl! { DivisorU64, u64 }
+    limbs_impl! { DivisorU64, u64 }
 }
 
 #[cfg(not(target_pointer_width = "64"))]
@@ -129,28 +422,178 @@ impl DivisorU64 {
 
 impl_traits! { DivisorU64, u64 }
 
+declare_cache_struct! { DivisorCacheU64, DivisorU64, u64, u64 }
+
+declare_const_struct! { ConstDivisorU64, DivisorU64, u64, "64-bit" }
+
+impl<const D: u64> ConstDivisorU64<D> {
+    const_unsigned_impl! { ConstDivisorU64, u64 }
+    const_forward_impl! { ConstDivisorU64, DivisorU64, MultiplesU64, u64, u64, u64 }
+}
+
+declare_adaptive_struct! { AdaptiveDivisorU64, InnerAdaptiveDivisorU64, DivisorU64, u64, "64-bit" }
+
+impl AdaptiveDivisorU64 {
+    unsigned_multiples_impl! { AdaptiveDivisorU64, MultiplesU64, u64 }
+}
+
+declare_digits_struct! { DigitsU64, DivisorU64, u64 }
+
+declare_mixed_radix_struct! { MixedRadixU64, DivisorU64, u64 }
+
+declare_multiples_struct! { MultiplesU64, DivisorU64, u64, u64 }
+
+declare_binner_struct! { BinnerU64, DivisorU64, u64, u64 }
+
+declare_even_chunks_struct! { EvenChunksU64, DivisorU64, u64 }
+
+declare_modulus_struct! { ModulusU64, DivisorU64, u64, "64-bit" }
+
+declare_montgomery_struct! { MontgomeryU64, DivisorU64, u64, "64-bit" }
+
+declare_range_reducer_struct! { RangeReducerU64, DivisorU64, u64, "64-bit" }
+
 tests! { DivisorU64, u64 }
 
+div_exact_tests! { DivisorU64, u64, div_exact_u64 }
+
+unsigned_mul_div_tests! { DivisorU64, u64, mul_div_u64 }
+
+div_rem_wide_tests! { DivisorU64, u64, div_rem_wide_u64 }
+
+gcd_tests! { DivisorU64, u64, i64, gcd_u64 }
+
+radix_tests! { DivisorU64, u64, radix_u64 }
+
+mixed_radix_tests! { MixedRadixU64, DivisorU64, u64, mixed_radix_u64 }
+
+unsigned_multiples_tests! { DivisorU64, u64, multiples_u64 }
+
+split_tests! { DivisorU64, u64, split_u64 }
+
+horner_tests! { DivisorU64, u64, horner_u64 }
+
+sample_tests! { DivisorU64, u64, sample_u64 }
+
+binner_tests! { BinnerU64, u64, u64, binner_u64 }
+
+range_reducer_tests! { RangeReducerU64, u64, u128, range_reducer_u64 }
+
+limbs_tests! { DivisorU64, u64, u128, limbs_u64 }
+
+crt_tests! { crt_u64 }
+
+rolling_hash_tests! { rolling_hash_u64 }
+
+prime_buckets_tests! { prime_buckets_u64 }
+
+cache_tests! { DivisorCacheU64, u64, cache_u64 }
+
+const_divisor_tests! { ConstDivisorU64, DivisorU64, u64, const_u64 }
+
+policy_tests! { AdaptiveDivisorU64, u64, policy_u64 }
+
+modulus_tests! { ModulusU64, u64, modulus_u64 }
+
+montgomery_tests! { MontgomeryU64, ModulusU64, u64, montgomery_u64 }
+
 // DivisorU128
 
 declare_unsigned_structs! { DivisorU128, InnerDivisorU128, u128, "128-bit" }
 
 impl DivisorU128 {
     unsigned_impl! { DivisorU128, InnerDivisorU128, u128 }
+    slice_impl! { DivisorU128, u128 }
+    unsigned_mul_div_impl! { DivisorU128, u128 }
+    unsigned_gcd_impl! { DivisorU128, u128, i128 }
+    radix_impl! { DivisorU128, DigitsU128, u128 }
+    unsigned_multiples_impl! { DivisorU128, MultiplesU128, u128 }
+    split_impl! { DivisorU128, EvenChunksU128, u128 }
+    horner_impl! { DivisorU128, u128 }
+    sample_impl! { DivisorU128, u128 }
     mulh_impl! { u128 }
     divlu_impl! { u128 }
 }
 
 impl_traits! { DivisorU128, u128 }
 
+declare_cache_struct! { DivisorCacheU128, DivisorU128, u128, u128 }
+
+declare_const_struct! { ConstDivisorU128, DivisorU128, u128, "128-bit" }
+
+impl<const D: u128> ConstDivisorU128<D> {
+    const_unsigned_impl! { ConstDivisorU128, u128 }
+    const_forward_impl! { ConstDivisorU128, DivisorU128, MultiplesU128, u128, u128, u128 }
+}
+
+declare_adaptive_struct! { AdaptiveDivisorU128, InnerAdaptiveDivisorU128, DivisorU128, u128, "128-bit" }
+
+impl AdaptiveDivisorU128 {
+    unsigned_multiples_impl! { AdaptiveDivisorU128, MultiplesU128, u128 }
+}
+
+declare_digits_struct! { DigitsU128, DivisorU128, u128 }
+
+declare_mixed_radix_struct! { MixedRadixU128, DivisorU128, u128 }
+
+declare_multiples_struct! { MultiplesU128, DivisorU128, u128, u128 }
+
+declare_binner_struct! { BinnerU128, DivisorU128, u128, u128 }
+
+declare_even_chunks_struct! { EvenChunksU128, DivisorU128, u128 }
+
+declare_modulus_struct! { ModulusU128, DivisorU128, u128, "128-bit" }
+
+declare_montgomery_struct! { MontgomeryU128, DivisorU128, u128, "128-bit" }
+
 tests! { DivisorU128, u128 }
 
+div_exact_tests! { DivisorU128, u128, div_exact_u128 }
+
+unsigned_mul_div_tests! { DivisorU128, u128, mul_div_u128 }
+
+div_rem_wide_tests! { DivisorU128, u128, div_rem_wide_u128 }
+
+gcd_tests! { DivisorU128, u128, i128, gcd_u128 }
+
+radix_tests! { DivisorU128, u128, radix_u128 }
+
+mixed_radix_tests! { MixedRadixU128, DivisorU128, u128, mixed_radix_u128 }
+
+unsigned_multiples_tests! { DivisorU128, u128, multiples_u128 }
+
+split_tests! { DivisorU128, u128, split_u128 }
+
+horner_tests! { DivisorU128, u128, horner_u128 }
+
+sample_tests! { DivisorU128, u128, sample_u128 }
+
+binner_tests! { BinnerU128, u128, u128, binner_u128 }
+
+cache_tests! { DivisorCacheU128, u128, cache_u128 }
+
+const_divisor_tests! { ConstDivisorU128, DivisorU128, u128, const_u128 }
+
+policy_tests! { AdaptiveDivisorU128, u128, policy_u128 }
+
+modulus_tests! { ModulusU128, u128, modulus_u128 }
+
+montgomery_tests! { MontgomeryU128, ModulusU128, u128, montgomery_u128 }
+
 // DivisorUsize
 
 declare_unsigned_structs! { DivisorUsize, InnerDivisorUsize, usize, "pointer-sized" }
 
 impl DivisorUsize {
     unsigned_impl! { DivisorUsize, InnerDivisorUsize, usize }
+    slice_impl! { DivisorUsize, usize }
+    unsigned_mul_div_impl! { DivisorUsize, usize }
+    unsigned_gcd_impl! { DivisorUsize, usize, isize }
+    radix_impl! { DivisorUsize, DigitsUsize, usize }
+    unsigned_multiples_impl! { DivisorUsize, MultiplesUsize, usize }
+    split_impl! { DivisorUsize, EvenChunksUsize, usize }
+    horner_impl! { DivisorUsize, usize }
+    sample_impl! { DivisorUsize, usize }
 }
 
 #[cfg(target_pointer_width = "16")]
@@ -173,54 +616,193 @@ impl DivisorUsize {
 
 impl_traits! { DivisorUsize, usize }
 
+declare_cache_struct! { DivisorCacheUsize, DivisorUsize, usize, usize }
+
+declare_const_struct! { ConstDivisorUsize, DivisorUsize, usize, "pointer-sized" }
+
+impl<const D: usize> ConstDivisorUsize<D> {
+    const_unsigned_impl! { ConstDivisorUsize, usize }
+    const_forward_impl! { ConstDivisorUsize, DivisorUsize, MultiplesUsize, usize, usize, usize }
+}
+
+declare_adaptive_struct! { AdaptiveDivisorUsize, InnerAdaptiveDivisorUsize, DivisorUsize, usize, "pointer-sized" }
+
+impl AdaptiveDivisorUsize {
+    unsigned_multiples_impl! { AdaptiveDivisorUsize, MultiplesUsize, usize }
+}
+
+declare_digits_struct! { DigitsUsize, DivisorUsize, usize }
+
+declare_mixed_radix_struct! { MixedRadixUsize, DivisorUsize, usize }
+
+declare_multiples_struct! { MultiplesUsize, DivisorUsize, usize, usize }
+
+declare_binner_struct! { BinnerUsize, DivisorUsize, usize, usize }
+
+declare_even_chunks_struct! { EvenChunksUsize, DivisorUsize, usize }
+
 // DivisorI8
 
-declare_signed_structs! { DivisorI8, InnerDivisorI8, i8, "8-bit" }
+declare_signed_structs! { DivisorI8, InnerDivisorI8, i8, u8, "8-bit" }
 
 impl DivisorI8 {
     signed_impl! { DivisorI8, InnerDivisorI8, i8, u8 }
+    signed_multiples_impl! { DivisorI8, MultiplesI8, i8, u8 }
+    slice_impl! { DivisorI8, i8 }
+    signed_mul_div_impl! { DivisorI8, i8, DivisorU8, u8 }
     widen_mulh_impl! { i8, i16 }
     widen_div_rem_impl! { u8, u16 }
 }
 
 impl_traits! { DivisorI8, i8 }
 
+declare_cache_struct! { DivisorCacheI8, DivisorI8, i8, u8 }
+
+declare_const_struct! { ConstDivisorI8, DivisorI8, i8, "8-bit" }
+
+impl<const D: i8> ConstDivisorI8<D> {
+    const_forward_impl! { ConstDivisorI8, DivisorI8, MultiplesI8, i8, Option<i8>, u8 }
+}
+
+declare_adaptive_struct! { AdaptiveDivisorI8, InnerAdaptiveDivisorI8, DivisorI8, i8, "8-bit" }
+
+impl AdaptiveDivisorI8 {
+    signed_multiples_impl! { AdaptiveDivisorI8, MultiplesI8, i8, u8 }
+}
+
+declare_multiples_struct! { MultiplesI8, DivisorI8, i8, u8 }
+
+declare_binner_struct! { BinnerI8, DivisorU8, i8, u8 }
+
 tests! { DivisorI8, i8 }
 
+div_exact_tests! { DivisorI8, i8, div_exact_i8 }
+
+signed_tests! { DivisorI8, i8, signed_i8 }
+
+signed_mul_div_tests! { DivisorI8, i8, mul_div_i8 }
+
+signed_multiples_tests! { DivisorI8, i8, u8, multiples_i8 }
+
+binner_tests! { BinnerI8, i8, u8, binner_i8 }
+
+cache_tests! { DivisorCacheI8, i8, cache_i8 }
+
+const_divisor_tests! { ConstDivisorI8, DivisorI8, i8, const_i8 }
+
+policy_tests! { AdaptiveDivisorI8, i8, policy_i8 }
+
 // DivisorI16
 
-declare_signed_structs! { DivisorI16, InnerDivisorI16, i16, "16-bit" }
+declare_signed_structs! { DivisorI16, InnerDivisorI16, i16, u16, "16-bit" }
 
 impl DivisorI16 {
     signed_impl! { DivisorI16, InnerDivisorI16, i16, u16 }
+    signed_multiples_impl! { DivisorI16, MultiplesI16, i16, u16 }
+    slice_impl! { DivisorI16, i16 }
+    signed_mul_div_impl! { DivisorI16, i16, DivisorU16, u16 }
     widen_mulh_impl! { i16, i32 }
     widen_div_rem_impl! { u16, u32 }
 }
 
 impl_traits! { DivisorI16, i16 }
 
+declare_cache_struct! { DivisorCacheI16, DivisorI16, i16, u16 }
+
+declare_const_struct! { ConstDivisorI16, DivisorI16, i16, "16-bit" }
+
+impl<const D: i16> ConstDivisorI16<D> {
+    const_forward_impl! { ConstDivisorI16, DivisorI16, MultiplesI16, i16, Option<i16>, u16 }
+}
+
+declare_adaptive_struct! { AdaptiveDivisorI16, InnerAdaptiveDivisorI16, DivisorI16, i16, "16-bit" }
+
+impl AdaptiveDivisorI16 {
+    signed_multiples_impl! { AdaptiveDivisorI16, MultiplesI16, i16, u16 }
+}
+
+declare_multiples_struct! { MultiplesI16, DivisorI16, i16, u16 }
+
+declare_binner_struct! { BinnerI16, DivisorU16, i16, u16 }
+
 tests! { DivisorI16, i16 }
 
+div_exact_tests! { DivisorI16, i16, div_exact_i16 }
+
+signed_tests! { DivisorI16, i16, signed_i16 }
+
+signed_mul_div_tests! { DivisorI16, i16, mul_div_i16 }
+
+signed_multiples_tests! { DivisorI16, i16, u16, multiples_i16 }
+
+binner_tests! { BinnerI16, i16, u16, binner_i16 }
+
+cache_tests! { DivisorCacheI16, i16, cache_i16 }
+
+const_divisor_tests! { ConstDivisorI16, DivisorI16, i16, const_i16 }
+
+policy_tests! { AdaptiveDivisorI16, i16, policy_i16 }
+
 // DivisorI32
 
-declare_signed_structs! { DivisorI32, InnerDivisorI32, i32, "32-bit" }
+declare_signed_structs! { DivisorI32, InnerDivisorI32, i32, u32, "32-bit" }
 
 impl DivisorI32 {
     signed_impl! { DivisorI32, InnerDivisorI32, i32, u32 }
+    signed_multiples_impl! { DivisorI32, MultiplesI32, i32, u32 }
+    slice_impl! { DivisorI32, i32 }
+    signed_mul_div_impl! { DivisorI32, i32, DivisorU32, u32 }
     widen_mulh_impl! { i32, i64 }
     widen_div_rem_impl! { u32, u64 }
 }
 
 impl_traits! { DivisorI32, i32 }
 
+declare_cache_struct! { DivisorCacheI32, DivisorI32, i32, u32 }
+
+declare_const_struct! { ConstDivisorI32, DivisorI32, i32, "32-bit" }
+
+impl<const D: i32> ConstDivisorI32<D> {
+    const_forward_impl! { ConstDivisorI32, DivisorI32, MultiplesI32, i32, Option<i32>, u32 }
+}
+
+declare_adaptive_struct! { AdaptiveDivisorI32, InnerAdaptiveDivisorI32, DivisorI32, i32, "32-bit" }
+
+impl AdaptiveDivisorI32 {
+    signed_multiples_impl! { AdaptiveDivisorI32, MultiplesI32, i32, u32 }
+}
+
+declare_multiples_struct! { MultiplesI32, DivisorI32, i32, u32 }
+
+declare_binner_struct! { BinnerI32, DivisorU32, i32, u32 }
+
 tests! { DivisorI32, i32 }
 
+div_exact_tests! { DivisorI32, i32, div_exact_i32 }
+
+signed_tests! { DivisorI32, i32, signed_i32 }
+
+signed_mul_div_tests! { DivisorI32, i32, mul_div_i32 }
+
+signed_multiples_tests! { DivisorI32, i32, u32, multiples_i32 }
+
+binner_tests! { BinnerI32, i32, u32, binner_i32 }
+
+cache_tests! { DivisorCacheI32, i32, cache_i32 }
+
+const_divisor_tests! { ConstDivisorI32, DivisorI32, i32, const_i32 }
+
+policy_tests! { AdaptiveDivisorI32, i32, policy_i32 }
+
 // DivisorI64
 
-declare_signed_structs! { DivisorI64, InnerDivisorI64, i64, "64-bit" }
+declare_signed_structs! { DivisorI64, InnerDivisorI64, i64, u64, "64-bit" }
 
 impl DivisorI64 {
     signed_impl! { DivisorI64, InnerDivisorI64, i64, u64 }
+    signed_multiples_impl! { DivisorI64, MultiplesI64, i64, u64 }
+    slice_impl! { DivisorI64, i64 }
+    signed_mul_div_impl! { DivisorI64, i64, DivisorU64, u64 }
 }
 
 #[cfg(not(target_pointer_width = "64"))]
@@ -237,28 +819,102 @@ impl DivisorI64 {
 
 impl_traits! { DivisorI64, i64 }
 
+declare_cache_struct! { DivisorCacheI64, DivisorI64, i64, u64 }
+
+declare_const_struct! { ConstDivisorI64, DivisorI64, i64, "64-bit" }
+
+impl<const D: i64> ConstDivisorI64<D> {
+    const_forward_impl! { ConstDivisorI64, DivisorI64, MultiplesI64, i64, Option<i64>, u64 }
+}
+
+declare_adaptive_struct! { AdaptiveDivisorI64, InnerAdaptiveDivisorI64, DivisorI64, i64, "64-bit" }
+
+impl AdaptiveDivisorI64 {
+    signed_multiples_impl! { AdaptiveDivisorI64, MultiplesI64, i64, u64 }
+}
+
+declare_multiples_struct! { MultiplesI64, DivisorI64, i64, u64 }
+
+declare_binner_struct! { BinnerI64, DivisorU64, i64, u64 }
+
 tests! { DivisorI64, i64 }
 
+div_exact_tests! { DivisorI64, i64, div_exact_i64 }
+
+signed_tests! { DivisorI64, i64, signed_i64 }
+
+signed_mul_div_tests! { DivisorI64, i64, mul_div_i64 }
+
+signed_multiples_tests! { DivisorI64, i64, u64, multiples_i64 }
+
+binner_tests! { BinnerI64, i64, u64, binner_i64 }
+
+cache_tests! { DivisorCacheI64, i64, cache_i64 }
+
+const_divisor_tests! { ConstDivisorI64, DivisorI64, i64, const_i64 }
+
+policy_tests! { AdaptiveDivisorI64, i64, policy_i64 }
+
 // DivisorI128
 
-declare_signed_structs! { DivisorI128, InnerDivisorI128, i128, "128-bit" }
+declare_signed_structs! { DivisorI128, InnerDivisorI128, i128, u128, "128-bit" }
 
 impl DivisorI128 {
     signed_impl! { DivisorI128, InnerDivisorI128, i128, u128 }
+    signed_multiples_impl! { DivisorI128, MultiplesI128, i128, u128 }
+    slice_impl! { DivisorI128, i128 }
+    signed_mul_div_impl! { DivisorI128, i128, DivisorU128, u128 }
     mulh_impl! { i128 }
     divlu_impl! { u128 }
 }
 
 impl_traits! { DivisorI128, i128 }
 
+declare_cache_struct! { DivisorCacheI128, DivisorI128, i128, u128 }
+
+declare_const_struct! { ConstDivisorI128, DivisorI128, i128, "128-bit" }
+
+impl<const D: i128> ConstDivisorI128<D> {
+    const_forward_impl! { ConstDivisorI128, DivisorI128, MultiplesI128, i128, Option<i128>, u128 }
+}
+
+declare_adaptive_struct! { AdaptiveDivisorI128, InnerAdaptiveDivisorI128, DivisorI128, i128, "128-bit" }
+
+impl AdaptiveDivisorI128 {
+    signed_multiples_impl! { AdaptiveDivisorI128, MultiplesI128, i128, u128 }
+}
+
+declare_multiples_struct! { MultiplesI128, DivisorI128, i128, u128 }
+
+declare_binner_struct! { BinnerI128, DivisorU128, i128, u128 }
+
 tests! { DivisorI128, i128 }
 
+div_exact_tests! { DivisorI128, i128, div_exact_i128 }
+
+signed_tests! { DivisorI128, i128, signed_i128 }
+
+signed_mul_div_tests! { DivisorI128, i128, mul_div_i128 }
+
+signed_multiples_tests! { DivisorI128, i128, u128, multiples_i128 }
+
+binner_tests! { BinnerI128, i128, u128, binner_i128 }
+
+cache_tests! { DivisorCacheI128, i128, cache_i128 }
+
+const_divisor_tests! { ConstDivisorI128, DivisorI128, i128, const_i128 }
+
+policy_tests! { AdaptiveDivisorI128, i128, policy_i128 }
+
 // DivisorIsize
 
-declare_signed_structs! { DivisorIsize, InnerDivisorIsize, isize, "pointer-sized" }
+declare_signed_structs! { DivisorIsize, InnerDivisorIsize, isize, usize, "pointer-sized" }
 
 impl DivisorIsize {
     signed_impl! { DivisorIsize, InnerDivisorIsize, isize, usize }
+    signed_multiples_impl! { DivisorIsize, MultiplesIsize, isize, usize }
+    slice_impl! { DivisorIsize, isize }
+    signed_mul_div_impl! { DivisorIsize, isize, DivisorUsize, usize }
 }
 
 #[cfg(target_pointer_width = "16")]
@@ -280,3 +936,241 @@ impl DivisorIsize {
 }
 
 impl_traits! { DivisorIsize, isize }
+
+declare_cache_struct! { DivisorCacheIsize, DivisorIsize, isize, usize }
+
+declare_const_struct! { ConstDivisorIsize, DivisorIsize, isize, "pointer-sized" }
+
+impl<const D: isize> ConstDivisorIsize<D> {
+    const_forward_impl! { ConstDivisorIsize, DivisorIsize, MultiplesIsize, isize, Option<isize>, usize }
+}
+
+declare_adaptive_struct! { AdaptiveDivisorIsize, InnerAdaptiveDivisorIsize, DivisorIsize, isize, "pointer-sized" }
+
+impl AdaptiveDivisorIsize {
+    signed_multiples_impl! { AdaptiveDivisorIsize, MultiplesIsize, isize, usize }
+}
+
+declare_multiples_struct! { MultiplesIsize, DivisorIsize, isize, usize }
+
+declare_binner_struct! { BinnerIsize, DivisorUsize, isize, usize }
+
+// ConstantTimeDivisorU8
+
+declare_constant_time_unsigned_struct! { ConstantTimeDivisorU8, u8, "8-bit" }
+
+impl ConstantTimeDivisorU8 {
+    constant_time_unsigned_impl! { ConstantTimeDivisorU8, u8 }
+    widen_mulh_impl! { u8, u16 }
+    widen_div_rem_impl! { u8, u16 }
+}
+
+impl_traits! { ConstantTimeDivisorU8, u8 }
+
+constant_time_tests! { ConstantTimeDivisorU8, u8, constant_time_u8 }
+
+// ConstantTimeDivisorU16
+
+declare_constant_time_unsigned_struct! { ConstantTimeDivisorU16, u16, "16-bit" }
+
+impl ConstantTimeDivisorU16 {
+    constant_time_unsigned_impl! { ConstantTimeDivisorU16, u16 }
+    widen_mulh_impl! { u16, u32 }
+    widen_div_rem_impl! { u16, u32 }
+}
+
+impl_traits! { ConstantTimeDivisorU16, u16 }
+
+constant_time_tests! { ConstantTimeDivisorU16, u16, constant_time_u16 }
+
+// ConstantTimeDivisorU32
+
+declare_constant_time_unsigned_struct! { ConstantTimeDivisorU32, u32, "32-bit" }
+
+impl ConstantTimeDivisorU32 {
+    constant_time_unsigned_impl! { ConstantTimeDivisorU32, u32 }
+    widen_mulh_impl! { u32, u64 }
+    widen_div_rem_impl! { u32, u64 }
+}
+
+impl_traits! { ConstantTimeDivisorU32, u32 }
+
+constant_time_tests! { ConstantTimeDivisorU32, u32, constant_time_u32 }
+
+// ConstantTimeDivisorU64
+
+declare_constant_time_unsigned_struct! { ConstantTimeDivisorU64, u64, "64-bit" }
+
+impl ConstantTimeDivisorU64 {
+    constant_time_unsigned_impl! { ConstantTimeDivisorU64, u64 }
+}
+
+#[cfg(not(target_pointer_width = "64"))]
+impl ConstantTimeDivisorU64 {
+    divlu_impl! { u64 }
+    mulh_impl! { u64 }
+}
+
+#[cfg(target_pointer_width = "64")]
+impl ConstantTimeDivisorU64 {
+    widen_div_rem_impl! { u64, u128 }
+    widen_mulh_impl! { u64, u128 }
+}
+
+impl_traits! { ConstantTimeDivisorU64, u64 }
+
+constant_time_tests! { ConstantTimeDivisorU64, u64, constant_time_u64 }
+
+// ConstantTimeDivisorU128
+
+declare_constant_time_unsigned_struct! { ConstantTimeDivisorU128, u128, "128-bit" }
+
+impl ConstantTimeDivisorU128 {
+    constant_time_unsigned_impl! { ConstantTimeDivisorU128, u128 }
+    mulh_impl! { u128 }
+    divlu_impl! { u128 }
+}
+
+impl_traits! { ConstantTimeDivisorU128, u128 }
+
+constant_time_tests! { ConstantTimeDivisorU128, u128, constant_time_u128 }
+
+// ConstantTimeDivisorUsize
+
+declare_constant_time_unsigned_struct! { ConstantTimeDivisorUsize, usize, "pointer-sized" }
+
+impl ConstantTimeDivisorUsize {
+    constant_time_unsigned_impl! { ConstantTimeDivisorUsize, usize }
+}
+
+#[cfg(target_pointer_width = "16")]
+impl ConstantTimeDivisorUsize {
+    widen_mulh_impl! { usize, u32 }
+    widen_div_rem_impl! { usize, u32 }
+}
+
+#[cfg(target_pointer_width = "32")]
+impl ConstantTimeDivisorUsize {
+    widen_mulh_impl! { usize, u64 }
+    widen_div_rem_impl! { usize, u64 }
+}
+
+#[cfg(target_pointer_width = "64")]
+impl ConstantTimeDivisorUsize {
+    widen_mulh_impl! { usize, u128 }
+    widen_div_rem_impl! { usize, u128 }
+}
+
+impl_traits! { ConstantTimeDivisorUsize, usize }
+
+constant_time_tests! { ConstantTimeDivisorUsize, usize, constant_time_usize }
+
+// ConstantTimeDivisorI8
+
+declare_constant_time_signed_struct! { ConstantTimeDivisorI8, i8, "8-bit" }
+
+impl ConstantTimeDivisorI8 {
+    constant_time_signed_impl! { ConstantTimeDivisorI8, i8, u8 }
+    widen_mulh_impl! { i8, i16 }
+    widen_div_rem_impl! { u8, u16 }
+}
+
+impl_traits! { ConstantTimeDivisorI8, i8 }
+
+constant_time_tests! { ConstantTimeDivisorI8, i8, constant_time_i8 }
+
+// ConstantTimeDivisorI16
+
+declare_constant_time_signed_struct! { ConstantTimeDivisorI16, i16, "16-bit" }
+
+impl ConstantTimeDivisorI16 {
+    constant_time_signed_impl! { ConstantTimeDivisorI16, i16, u16 }
+    widen_mulh_impl! { i16, i32 }
+    widen_div_rem_impl! { u16, u32 }
+}
+
+impl_traits! { ConstantTimeDivisorI16, i16 }
+
+constant_time_tests! { ConstantTimeDivisorI16, i16, constant_time_i16 }
+
+// ConstantTimeDivisorI32
+
+declare_constant_time_signed_struct! { ConstantTimeDivisorI32, i32, "32-bit" }
+
+impl ConstantTimeDivisorI32 {
+    constant_time_signed_impl! { ConstantTimeDivisorI32, i32, u32 }
+    widen_mulh_impl! { i32, i64 }
+    widen_div_rem_impl! { u32, u64 }
+}
+
+impl_traits! { ConstantTimeDivisorI32, i32 }
+
+constant_time_tests! { ConstantTimeDivisorI32, i32, constant_time_i32 }
+
+// ConstantTimeDivisorI64
+
+declare_constant_time_signed_struct! { ConstantTimeDivisorI64, i64, "64-bit" }
+
+impl ConstantTimeDivisorI64 {
+    constant_time_signed_impl! { ConstantTimeDivisorI64, i64, u64 }
+}
+
+#[cfg(not(target_pointer_width = "64"))]
+impl ConstantTimeDivisorI64 {
+    mulh_impl! { i64 }
+    divlu_impl! { u64 }
+}
+
+#[cfg(target_pointer_width = "64")]
+impl ConstantTimeDivisorI64 {
+    widen_mulh_impl! { i64, i128 }
+    widen_div_rem_impl! { u64, u128 }
+}
+
+impl_traits! { ConstantTimeDivisorI64, i64 }
+
+constant_time_tests! { ConstantTimeDivisorI64, i64, constant_time_i64 }
+
+// ConstantTimeDivisorI128
+
+declare_constant_time_signed_struct! { ConstantTimeDivisorI128, i128, "128-bit" }
+
+impl ConstantTimeDivisorI128 {
+    constant_time_signed_impl! { ConstantTimeDivisorI128, i128, u128 }
+    mulh_impl! { i128 }
+    divlu_impl! { u128 }
+}
+
+impl_traits! { ConstantTimeDivisorI128, i128 }
+
+constant_time_tests! { ConstantTimeDivisorI128, i128, constant_time_i128 }
+
+// ConstantTimeDivisorIsize
+
+declare_constant_time_signed_struct! { ConstantTimeDivisorIsize, isize, "pointer-sized" }
+
+impl ConstantTimeDivisorIsize {
+    constant_time_signed_impl! { ConstantTimeDivisorIsize, isize, usize }
+}
+
+#[cfg(target_pointer_width = "16")]
+impl ConstantTimeDivisorIsize {
+    widen_mulh_impl! { isize, i32 }
+    widen_div_rem_impl! { usize, u32 }
+}
+
+#[cfg(target_pointer_width = "32")]
+impl ConstantTimeDivisorIsize {
+    widen_mulh_impl! { isize, i64 }
+    widen_div_rem_impl! { usize, u64 }
+}
+
+#[cfg(target_pointer_width = "64")]
+impl ConstantTimeDivisorIsize {
+    widen_mulh_impl! { isize, i128 }
+    widen_div_rem_impl! { usize, u128 }
+}
+
+impl_traits! { ConstantTimeDivisorIsize, isize }
+
+constant_time_tests! { ConstantTimeDivisorIsize, isize, constant_time_isize }
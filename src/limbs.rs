@@ -0,0 +1,167 @@
+macro_rules! limbs_impl {
+    ($SelfT:ident, $BaseT:ty) => {
+        /// Divides the multi-precision integer stored in `limbs` by `self` in
+        /// place, and returns the remainder.
+        ///
+        /// The limbs are in little-endian order, i.e. the least significant limb
+        /// comes first. Each limb is divided using the precomputed reciprocal of
+        /// the divisor, as in [`div_rem_wide`](Self::div_rem_wide).
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(10);")]
+        /// // 5 * 2^N + 3
+        /// let mut limbs = [3, 5];
+        /// let r = d.div_limbs_in_place(&mut limbs);
+        #[doc = concat!("assert_eq!(limbs, [", stringify!($BaseT), "::MAX / 10 * 5 + 3, 0]);")]
+        /// assert_eq!(r, 3);
+        /// ```
+        pub fn div_limbs_in_place(&self, limbs: &mut [$BaseT]) -> $BaseT {
+            self.div_limbs_iter(limbs.iter_mut().rev())
+        }
+
+        /// Same as [`div_limbs_in_place`](Self::div_limbs_in_place), but with the
+        /// limbs in big-endian order, i.e. the most significant limb comes first.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(10);")]
+        /// let mut limbs = [5, 3];
+        /// let r = d.div_limbs_in_place_be(&mut limbs);
+        #[doc = concat!("assert_eq!(limbs, [0, ", stringify!($BaseT), "::MAX / 10 * 5 + 3]);")]
+        /// assert_eq!(r, 3);
+        /// ```
+        pub fn div_limbs_in_place_be(&self, limbs: &mut [$BaseT]) -> $BaseT {
+            self.div_limbs_iter(limbs.iter_mut())
+        }
+
+        /// Returns the remainder of dividing the multi-precision integer stored
+        /// in `limbs` by `self`.
+        ///
+        /// The limbs are in little-endian order, i.e. the least significant limb
+        /// comes first.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(10);")]
+        /// assert_eq!(d.rem_limbs(&[3, 5]), 3);
+        /// ```
+        pub fn rem_limbs(&self, limbs: &[$BaseT]) -> $BaseT {
+            self.rem_limbs_iter(limbs.iter().rev().copied())
+        }
+
+        /// Same as [`rem_limbs`](Self::rem_limbs), but with the limbs in
+        /// big-endian order, i.e. the most significant limb comes first.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(10);")]
+        /// assert_eq!(d.rem_limbs_be(&[5, 3]), 3);
+        /// ```
+        pub fn rem_limbs_be(&self, limbs: &[$BaseT]) -> $BaseT {
+            self.rem_limbs_iter(limbs.iter().copied())
+        }
+
+        // Schoolbook short division, starting from the most significant limb.
+        // The running remainder is always less than the divisor, so each step
+        // is a valid 2-by-1 division.
+        #[inline]
+        fn div_limbs_iter<'a, I: Iterator<Item = &'a mut $BaseT>>(&self, limbs: I) -> $BaseT {
+            let d = self.get();
+            let mut r = 0;
+            for limb in limbs {
+                let (q, rem) = Self::div_rem_wide_preinv(r, *limb, d, self.reciprocal);
+                *limb = q;
+                r = rem;
+            }
+            r
+        }
+
+        #[inline]
+        fn rem_limbs_iter<I: Iterator<Item = $BaseT>>(&self, limbs: I) -> $BaseT {
+            let d = self.get();
+            limbs.fold(0, |r, limb| {
+                Self::div_rem_wide_preinv(r, limb, d, self.reciprocal).1
+            })
+        }
+    };
+}
+
+macro_rules! limbs_tests {
+    ($SelfT:ident, $BaseT:ident, $WiderT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_lossless, clippy::cast_possible_truncation)]
+        mod $ModName {
+            use quickcheck::*;
+            use std::vec::Vec;
+
+            use super::*;
+
+            // Computes q * d + r for little-endian limbs, returning the result
+            // with one extra limb on top.
+            fn mul_add_limbs(q: &[$BaseT], d: $BaseT, r: $BaseT) -> Vec<$BaseT> {
+                let mut carry = r as $WiderT;
+                let mut result: Vec<$BaseT> = q
+                    .iter()
+                    .map(|&limb| {
+                        let t = limb as $WiderT * d as $WiderT + carry;
+                        carry = t >> <$BaseT>::BITS;
+                        t as $BaseT
+                    })
+                    .collect();
+                result.push(carry as $BaseT);
+                result
+            }
+
+            #[test]
+            fn handles_empty_slices() {
+                let d = $SelfT::new(7);
+                assert_eq!(d.div_limbs_in_place(&mut []), 0);
+                assert_eq!(d.rem_limbs_be(&[]), 0);
+            }
+
+            quickcheck! {
+                fn division_is_invertible(limbs: Vec<$BaseT>, d: $SelfT) -> bool {
+                    let mut q = limbs.clone();
+                    let r = d.div_limbs_in_place(&mut q);
+
+                    let mut expected = limbs.clone();
+                    expected.push(0);
+                    r < d.get() && mul_add_limbs(&q, d.get(), r) == expected && d.rem_limbs(&limbs) == r
+                }
+            }
+
+            quickcheck! {
+                fn endianness_variants_agree(limbs: Vec<$BaseT>, d: $SelfT) -> bool {
+                    let mut le = limbs.clone();
+                    let mut be: Vec<$BaseT> = limbs.iter().rev().copied().collect();
+
+                    let r = d.div_limbs_in_place(&mut le);
+                    let r_be = d.div_limbs_in_place_be(&mut be);
+                    be.reverse();
+
+                    r == r_be && le == be && d.rem_limbs_be(&limbs.iter().rev().copied().collect::<Vec<_>>()) == r
+                }
+            }
+
+            quickcheck! {
+                fn agrees_with_wide_division(lo: $BaseT, hi: $BaseT, d: $SelfT) -> bool {
+                    let n = (hi as $WiderT) << <$BaseT>::BITS | lo as $WiderT;
+                    let mut limbs = [lo, hi];
+                    let r = d.div_limbs_in_place(&mut limbs);
+
+                    let q = n / d.get() as $WiderT;
+                    limbs == [q as $BaseT, (q >> <$BaseT>::BITS) as $BaseT] && r as $WiderT == n % d.get() as $WiderT
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,168 @@
+macro_rules! declare_mixed_radix_struct {
+    ($SelfT:ident, $DivisorT:ident, $BaseT:ty) => {
+        /// Mixed-radix number system with `N` digits, where the radix of each
+        /// digit is given by a
+        #[doc = concat!("[`", stringify!($DivisorT), "`].")]
+        ///
+        /// Digits are ordered from the most significant to the least significant,
+        /// so that the last digit changes the fastest. This matches the row-major
+        /// layout of an `N`-dimensional array, whose shape is given by the
+        /// radices.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::{", stringify!($DivisorT), ", ", stringify!($SelfT), "};")]
+        ///
+        /// // Days, hours, minutes and seconds in a week.
+        #[doc = concat!("let week = ", stringify!($SelfT), "::new([")]
+        #[doc = concat!("    ", stringify!($DivisorT), "::new(7),")]
+        #[doc = concat!("    ", stringify!($DivisorT), "::new(24),")]
+        #[doc = concat!("    ", stringify!($DivisorT), "::new(60),")]
+        #[doc = concat!("    ", stringify!($DivisorT), "::new(60),")]
+        /// ]);
+        ///
+        /// assert_eq!(week.unravel(200), Some([0, 0, 3, 20]));
+        /// assert_eq!(week.ravel([0, 0, 3, 20]), Some(200));
+        /// ```
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub struct $SelfT<const N: usize> {
+            radices: [$DivisorT; N],
+        }
+
+        impl<const N: usize> $SelfT<N> {
+            /// Creates a mixed-radix number system with the given radices, from
+            /// the most significant digit to the least significant one.
+            #[inline]
+            pub const fn new(radices: [$DivisorT; N]) -> Self {
+                Self { radices }
+            }
+
+            /// Returns the radices of this number system, from the most
+            /// significant digit to the least significant one.
+            #[inline]
+            pub const fn radices(&self) -> &[$DivisorT; N] {
+                &self.radices
+            }
+
+            /// Decomposes `n` into its digits, from the most significant to the
+            /// least significant one, or returns `None` if `n` is not less than
+            /// the product of all the radices.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::{", stringify!($DivisorT), ", ", stringify!($SelfT), "};")]
+            ///
+            #[doc = concat!("let shape = ", stringify!($SelfT), "::new([", stringify!($DivisorT), "::new(2), ", stringify!($DivisorT), "::new(3), ", stringify!($DivisorT), "::new(4)]);")]
+            /// assert_eq!(shape.unravel(23), Some([1, 2, 3]));
+            /// assert_eq!(shape.unravel(24), None);
+            /// ```
+            pub fn unravel(&self, n: $BaseT) -> Option<[$BaseT; N]> {
+                let mut digits = [0; N];
+                let mut n = n;
+                for (digit, radix) in digits.iter_mut().zip(&self.radices).rev() {
+                    let q = radix.div_of(n);
+                    *digit = n - q * radix.get();
+                    n = q;
+                }
+
+                if n == 0 {
+                    Some(digits)
+                } else {
+                    None
+                }
+            }
+
+            /// Composes a value from its digits, from the most significant to the
+            /// least significant one, or returns `None` if any of the digits is
+            /// not less than its radix, or if the value would overflow.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::{", stringify!($DivisorT), ", ", stringify!($SelfT), "};")]
+            ///
+            #[doc = concat!("let shape = ", stringify!($SelfT), "::new([", stringify!($DivisorT), "::new(2), ", stringify!($DivisorT), "::new(3), ", stringify!($DivisorT), "::new(4)]);")]
+            /// assert_eq!(shape.ravel([1, 2, 3]), Some(23));
+            /// assert_eq!(shape.ravel([1, 3, 0]), None);
+            /// ```
+            pub fn ravel(&self, digits: [$BaseT; N]) -> Option<$BaseT> {
+                let mut n: $BaseT = 0;
+                for (&digit, radix) in digits.iter().zip(&self.radices) {
+                    if digit >= radix.get() {
+                        return None;
+                    }
+                    n = n.checked_mul(radix.get())?.checked_add(digit)?;
+                }
+                Some(n)
+            }
+        }
+    };
+}
+
+macro_rules! mixed_radix_tests {
+    ($MixedRadixT:ident, $DivisorT:ident, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            fn radices(ds: [$BaseT; 3]) -> $MixedRadixT<3> {
+                let mut radices = [$DivisorT::new(1); 3];
+                for (radix, &d) in radices.iter_mut().zip(&ds) {
+                    *radix = $DivisorT::new(d.max(1));
+                }
+                $MixedRadixT::new(radices)
+            }
+
+            fn reference_capacity(mr: &$MixedRadixT<3>) -> Option<$BaseT> {
+                mr.radices().iter().try_fold(1 as $BaseT, |acc, d| acc.checked_mul(d.get()))
+            }
+
+            #[test]
+            fn handles_no_digits() {
+                let mr = <$MixedRadixT<0>>::new([]);
+                assert_eq!(mr.unravel(0), Some([]));
+                assert_eq!(mr.unravel(1), None);
+                assert_eq!(mr.ravel([]), Some(0));
+            }
+
+            #[test]
+            fn detects_overflow() {
+                let max = $DivisorT::new(<$BaseT>::MAX);
+                let mr = $MixedRadixT::new([max, max]);
+                assert_eq!(mr.ravel([0, <$BaseT>::MAX - 1]), Some(<$BaseT>::MAX - 1));
+                assert_eq!(mr.ravel([1, 0]), Some(<$BaseT>::MAX));
+                assert_eq!(mr.ravel([1, 1]), None);
+                assert_eq!(mr.unravel(<$BaseT>::MAX), Some([1, 0]));
+            }
+
+            quickcheck! {
+                fn ravel_undoes_unravel(ds: ($BaseT, $BaseT, $BaseT), n: $BaseT) -> bool {
+                    let mr = radices([ds.0, ds.1, ds.2]);
+                    match mr.unravel(n) {
+                        Some(digits) => {
+                            digits.iter().zip(mr.radices()).all(|(&digit, d)| digit < d.get())
+                                && mr.ravel(digits) == Some(n)
+                        }
+                        None => reference_capacity(&mr).map_or(false, |capacity| n >= capacity),
+                    }
+                }
+            }
+
+            quickcheck! {
+                fn ravel_agrees_with_checked_arithmetic(ds: ($BaseT, $BaseT, $BaseT), digits: ($BaseT, $BaseT, $BaseT)) -> bool {
+                    let mr = radices([ds.0, ds.1, ds.2]);
+                    let digits = [digits.0, digits.1, digits.2];
+
+                    let valid = digits.iter().zip(mr.radices()).all(|(&digit, d)| digit < d.get());
+                    let expected = digits
+                        .iter()
+                        .zip(mr.radices())
+                        .try_fold(0 as $BaseT, |acc, (&digit, d)| acc.checked_mul(d.get())?.checked_add(digit))
+                        .filter(|_| valid);
+                    mr.ravel(digits) == expected
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,289 @@
+macro_rules! declare_modulus_struct {
+    ($SelfT:ident, $DivisorT:ident, $BaseT:ty, $SIZE:literal) => {
+        /// Modulus for arithmetic on
+        #[doc = concat!($SIZE)]
+        /// unsigned integers modulo a value known only at runtime.
+        ///
+        /// All operations accept arbitrary operands, reducing them first if
+        /// necessary, and return a result less than the modulus. Products are
+        /// computed in full double-width precision and reduced using the
+        /// precomputed reciprocal of the modulus, a variant of Barrett reduction,
+        /// so nothing overflows and no hardware division is needed.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let m = ", stringify!($SelfT), "::new(1_000_000_007);")]
+        /// assert_eq!(m.add_mod(1_000_000_000, 10), 3);
+        /// assert_eq!(m.sub_mod(3, 10), 1_000_000_000);
+        /// assert_eq!(m.pow_mod(2, 1_000_000_006), 1);
+        /// ```
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $SelfT {
+            divisor: $DivisorT,
+        }
+
+        impl $SelfT {
+            /// Creates a modulus for arithmetic modulo `m`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `m` equals zero.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// ```
+            #[inline]
+            pub const fn new(m: $BaseT) -> Self {
+                Self {
+                    divisor: <$DivisorT>::new(m),
+                }
+            }
+
+            /// Returns the value of the modulus as a primitive type.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// assert_eq!(m.get(), 97);
+            /// ```
+            #[inline]
+            pub const fn get(&self) -> $BaseT {
+                self.divisor.get()
+            }
+
+            /// Returns the divisor used to reduce values modulo `self`.
+            #[inline]
+            pub const fn divisor(&self) -> $DivisorT {
+                self.divisor
+            }
+
+            /// Returns `a` reduced modulo `self`.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// assert_eq!(m.reduce(200), 6);
+            /// ```
+            #[inline]
+            pub const fn reduce(&self, a: $BaseT) -> $BaseT {
+                self.divisor.rem_of(a)
+            }
+
+            /// Returns `(a + b) mod self`.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(", stringify!($BaseT), "::MAX - 1);")]
+            #[doc = concat!("assert_eq!(m.add_mod(", stringify!($BaseT), "::MAX - 2, 5), 4);")]
+            /// ```
+            #[inline]
+            pub const fn add_mod(&self, a: $BaseT, b: $BaseT) -> $BaseT {
+                let m = self.get();
+                let (a, b) = (self.reduce(a), self.reduce(b));
+
+                let (sum, overflowed) = a.overflowing_add(b);
+                if overflowed || sum >= m {
+                    sum.wrapping_sub(m)
+                } else {
+                    sum
+                }
+            }
+
+            /// Returns `(a - b) mod self`.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// assert_eq!(m.sub_mod(3, 5), 95);
+            /// ```
+            #[inline]
+            pub const fn sub_mod(&self, a: $BaseT, b: $BaseT) -> $BaseT {
+                let m = self.get();
+                let (a, b) = (self.reduce(a), self.reduce(b));
+
+                if a >= b {
+                    a - b
+                } else {
+                    a.wrapping_sub(b).wrapping_add(m)
+                }
+            }
+
+            /// Returns `-a mod self`.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// assert_eq!(m.neg_mod(5), 92);
+            /// assert_eq!(m.neg_mod(0), 0);
+            /// ```
+            #[inline]
+            pub const fn neg_mod(&self, a: $BaseT) -> $BaseT {
+                self.sub_mod(0, a)
+            }
+
+            /// Returns `(a * b) mod self`.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(", stringify!($BaseT), "::MAX);")]
+            #[doc = concat!("assert_eq!(m.mul_mod(", stringify!($BaseT), "::MAX - 1, ", stringify!($BaseT), "::MAX - 1), 1);")]
+            /// ```
+            #[inline]
+            pub fn mul_mod(&self, a: $BaseT, b: $BaseT) -> $BaseT {
+                self.divisor.mul_rem(a, b)
+            }
+
+            /// Returns `base^exp mod self`, computed by repeated squaring.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// assert_eq!(m.pow_mod(3, 4), 81);
+            /// assert_eq!(m.pow_mod(5, 96), 1);
+            /// assert_eq!(m.pow_mod(0, 0), 1);
+            /// ```
+            pub fn pow_mod(&self, base: $BaseT, exp: $BaseT) -> $BaseT {
+                let mut result = self.reduce(1);
+                let mut base = self.reduce(base);
+                let mut exp = exp;
+
+                while exp > 0 {
+                    if exp & 1 == 1 {
+                        result = self.mul_mod(result, base);
+                    }
+                    base = self.mul_mod(base, base);
+                    exp >>= 1;
+                }
+
+                result
+            }
+        }
+
+        impl core::fmt::Debug for $SelfT {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{}", self.get())
+            }
+        }
+
+        impl From<$DivisorT> for $SelfT {
+            #[inline]
+            fn from(divisor: $DivisorT) -> $SelfT {
+                Self { divisor }
+            }
+        }
+    };
+}
+
+macro_rules! modulus_tests {
+    ($ModulusT:ident, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_lossless)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            reference_wide_impl! { $BaseT }
+
+            fn reference_mul_mod(a: $BaseT, b: $BaseT, m: $BaseT) -> $BaseT {
+                let (hi, lo) = reference_mul_wide(a, b);
+                reference_div_rem_wide(hi % m, lo, m).1
+            }
+
+            #[test]
+            #[should_panic(expected = "attempt to divide by zero")]
+            fn cannot_create_zero_modulus() {
+                let _ = $ModulusT::new(0);
+            }
+
+            #[test]
+            fn modulus_one_maps_everything_to_zero() {
+                let m = $ModulusT::new(1);
+                assert_eq!(m.add_mod(<$BaseT>::MAX, 1), 0);
+                assert_eq!(m.neg_mod(<$BaseT>::MAX), 0);
+                assert_eq!(m.mul_mod(<$BaseT>::MAX, <$BaseT>::MAX), 0);
+                assert_eq!(m.pow_mod(2, 0), 0);
+            }
+
+            quickcheck! {
+                fn add_mod_agrees_with_wide_sum(a: $BaseT, b: $BaseT, m: $BaseT) -> TestResult {
+                    if m == 0 {
+                        return TestResult::discard();
+                    }
+                    let (a, b) = (a % m, b % m);
+                    let expected = match a.checked_add(b) {
+                        Some(sum) => sum % m,
+                        None => a.wrapping_add(b).wrapping_sub(m),
+                    };
+                    TestResult::from_bool($ModulusT::new(m).add_mod(a, b) == expected)
+                }
+            }
+
+            quickcheck! {
+                fn sub_mod_undoes_add_mod(a: $BaseT, b: $BaseT, m: $BaseT) -> TestResult {
+                    if m == 0 {
+                        return TestResult::discard();
+                    }
+                    let modulus = $ModulusT::new(m);
+                    let sum = modulus.add_mod(a, b);
+                    TestResult::from_bool(
+                        modulus.sub_mod(sum, b) == a % m && modulus.add_mod(modulus.neg_mod(a), a) == 0,
+                    )
+                }
+            }
+
+            quickcheck! {
+                fn mul_mod_agrees_with_long_multiplication(a: $BaseT, b: $BaseT, m: $BaseT) -> TestResult {
+                    if m == 0 {
+                        return TestResult::discard();
+                    }
+                    TestResult::from_bool($ModulusT::new(m).mul_mod(a, b) == reference_mul_mod(a, b, m))
+                }
+            }
+
+            quickcheck! {
+                fn pow_mod_agrees_with_repeated_multiplication(base: $BaseT, exp: u8, m: $BaseT) -> TestResult {
+                    if m == 0 {
+                        return TestResult::discard();
+                    }
+                    let mut expected = 1 % m;
+                    for _ in 0..exp {
+                        expected = reference_mul_mod(expected, base, m);
+                    }
+                    TestResult::from_bool($ModulusT::new(m).pow_mod(base, exp as $BaseT) == expected)
+                }
+            }
+
+            quickcheck! {
+                fn pow_mod_adds_exponents(base: $BaseT, e1: $BaseT, e2: $BaseT, m: $BaseT) -> TestResult {
+                    if m == 0 {
+                        return TestResult::discard();
+                    }
+                    let (e1, e2) = (e1 >> 1, e2 >> 1);
+                    let modulus = $ModulusT::new(m);
+                    let product = modulus.mul_mod(modulus.pow_mod(base, e1), modulus.pow_mod(base, e2));
+                    TestResult::from_bool(modulus.pow_mod(base, e1 + e2) == product)
+                }
+            }
+        }
+    };
+}
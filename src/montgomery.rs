@@ -0,0 +1,338 @@
+macro_rules! declare_montgomery_struct {
+    ($SelfT:ident, $DivisorT:ident, $BaseT:ty, $SIZE:literal) => {
+        /// Modulus for arithmetic on
+        #[doc = concat!($SIZE)]
+        /// unsigned integers in Montgomery form, modulo an odd value known only
+        /// at runtime.
+        ///
+        /// A value `x` is represented in Montgomery form as `x * R mod m`, where
+        #[doc = concat!("`R = 2^", stringify!($BaseT), "::BITS`.")]
+        /// Converting into and out of this form costs about as much as a single
+        /// modular multiplication, but multiplication of values already in
+        /// Montgomery form needs no division at all, which makes it the faster
+        /// choice for long chains of multiplications such as modular
+        /// exponentiation.
+        ///
+        /// All methods other than [`to_montgomery`](Self::to_montgomery) expect
+        /// values in Montgomery form, which are always less than the modulus.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let m = ", stringify!($SelfT), "::new(1_000_000_007);")]
+        /// let (a, b) = (m.to_montgomery(123_456_789), m.to_montgomery(987_654_321));
+        /// assert_eq!(m.from_montgomery(m.mul(a, b)), 259_106_859);
+        /// assert_eq!(m.from_montgomery(m.pow(a, 1_000_000_006)), 1);
+        /// ```
+        #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $SelfT {
+            divisor: $DivisorT,
+            // The inverse of the modulus, modulo R.
+            inverse: $BaseT,
+            // R mod m, i.e. one in Montgomery form.
+            one: $BaseT,
+            // R^2 mod m, used to convert values into Montgomery form.
+            r2: $BaseT,
+        }
+
+        impl $SelfT {
+            /// Creates a modulus for Montgomery arithmetic modulo `m`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `m` is even, including when it equals zero.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// ```
+            pub const fn new(m: $BaseT) -> Self {
+                // Forces a panic when m is even, since we cannot use panic! in const.
+                let _ = 1 / (m & 1);
+
+                Self::from_divisor(<$DivisorT>::new(m))
+            }
+
+            /// Creates a modulus for Montgomery arithmetic modulo the value of
+            /// `divisor`, which is then used to reduce values converted into
+            /// Montgomery form.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value of `divisor` is even.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::{", stringify!($DivisorT), ", ", stringify!($SelfT), "};")]
+            ///
+            #[doc = concat!("let d = ", stringify!($DivisorT), "::new(97);")]
+            #[doc = concat!("let m = ", stringify!($SelfT), "::from_divisor(d);")]
+            /// assert_eq!(m.get(), 97);
+            /// ```
+            pub const fn from_divisor(divisor: $DivisorT) -> Self {
+                let m = divisor.get();
+                let _ = 1 / (m & 1);
+
+                let inverse = <$DivisorT>::odd_inverse(m);
+                let one = <$DivisorT>::div_rem_wide_by_base(1 % m, 0, m).1;
+                let r2 = <$DivisorT>::div_rem_wide_by_base(<$DivisorT>::mulh(one, one), one.wrapping_mul(one), m).1;
+
+                Self {
+                    divisor,
+                    inverse,
+                    one,
+                    r2,
+                }
+            }
+
+            /// Returns the value of the modulus as a primitive type.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// assert_eq!(m.get(), 97);
+            /// ```
+            #[inline]
+            pub const fn get(&self) -> $BaseT {
+                self.divisor.get()
+            }
+
+            /// Returns one in Montgomery form.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// assert_eq!(m.from_montgomery(m.one()), 1);
+            /// ```
+            #[inline]
+            pub const fn one(&self) -> $BaseT {
+                self.one
+            }
+
+            /// Converts `x` into Montgomery form.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// assert_eq!(m.from_montgomery(m.to_montgomery(200)), 6);
+            /// ```
+            #[inline]
+            pub const fn to_montgomery(&self, x: $BaseT) -> $BaseT {
+                let x = self.divisor.rem_of(x);
+                self.redc(<$DivisorT>::mulh(x, self.r2), x.wrapping_mul(self.r2))
+            }
+
+            /// Converts `x` out of Montgomery form.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// assert_eq!(m.from_montgomery(m.to_montgomery(42)), 42);
+            /// ```
+            #[inline]
+            pub const fn from_montgomery(&self, x: $BaseT) -> $BaseT {
+                self.redc(0, x)
+            }
+
+            /// Returns the Montgomery product of `a` and `b`, i.e. the Montgomery
+            /// form of the product of the values they represent.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// let product = m.mul(m.to_montgomery(10), m.to_montgomery(20));
+            /// assert_eq!(m.from_montgomery(product), 200 % 97);
+            /// ```
+            #[inline]
+            pub const fn mul(&self, a: $BaseT, b: $BaseT) -> $BaseT {
+                self.redc(<$DivisorT>::mulh(a, b), a.wrapping_mul(b))
+            }
+
+            /// Returns the Montgomery square of `a`.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// assert_eq!(m.from_montgomery(m.square(m.to_montgomery(10))), 3);
+            /// ```
+            #[inline]
+            pub const fn square(&self, a: $BaseT) -> $BaseT {
+                self.mul(a, a)
+            }
+
+            /// Returns `base` raised to the power of `exp`, computed by repeated
+            /// squaring. Only `base` and the result are in Montgomery form.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// assert_eq!(m.from_montgomery(m.pow(m.to_montgomery(3), 4)), 81);
+            /// assert_eq!(m.from_montgomery(m.pow(m.to_montgomery(5), 96)), 1);
+            /// ```
+            pub const fn pow(&self, base: $BaseT, exp: $BaseT) -> $BaseT {
+                let mut result = self.one;
+                let mut base = base;
+                let mut exp = exp;
+
+                while exp > 0 {
+                    if exp & 1 == 1 {
+                        result = self.mul(result, base);
+                    }
+                    base = self.square(base);
+                    exp >>= 1;
+                }
+
+                result
+            }
+
+            /// Returns the sum of `a` and `b`.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// let sum = m.add(m.to_montgomery(90), m.to_montgomery(10));
+            /// assert_eq!(m.from_montgomery(sum), 3);
+            /// ```
+            #[inline]
+            pub const fn add(&self, a: $BaseT, b: $BaseT) -> $BaseT {
+                let m = self.get();
+                let (sum, overflowed) = a.overflowing_add(b);
+                if overflowed || sum >= m {
+                    sum.wrapping_sub(m)
+                } else {
+                    sum
+                }
+            }
+
+            /// Returns the difference of `a` and `b`.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let m = ", stringify!($SelfT), "::new(97);")]
+            /// let difference = m.sub(m.to_montgomery(3), m.to_montgomery(5));
+            /// assert_eq!(m.from_montgomery(difference), 95);
+            /// ```
+            #[inline]
+            pub const fn sub(&self, a: $BaseT, b: $BaseT) -> $BaseT {
+                if a >= b {
+                    a - b
+                } else {
+                    a.wrapping_sub(b).wrapping_add(self.get())
+                }
+            }
+
+            // Montgomery reduction of hi * R + lo < m * R, returning
+            // (hi * R + lo) / R mod m. Since q * m agrees with lo in the bottom
+            // half, subtracting it leaves only the difference of the top halves.
+            #[inline]
+            const fn redc(&self, hi: $BaseT, lo: $BaseT) -> $BaseT {
+                let m = self.get();
+                let q = lo.wrapping_mul(self.inverse);
+                let qm_hi = <$DivisorT>::mulh(q, m);
+
+                if hi >= qm_hi {
+                    hi - qm_hi
+                } else {
+                    hi.wrapping_sub(qm_hi).wrapping_add(m)
+                }
+            }
+        }
+
+        impl core::fmt::Debug for $SelfT {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{}", self.get())
+            }
+        }
+    };
+}
+
+macro_rules! montgomery_tests {
+    ($MontgomeryT:ident, $ModulusT:ident, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            #[derive(Clone, Copy, Debug)]
+            struct OddModulus($BaseT);
+
+            impl Arbitrary for OddModulus {
+                fn arbitrary(g: &mut Gen) -> Self {
+                    OddModulus(<$BaseT>::arbitrary(g) | 1)
+                }
+            }
+
+            #[test]
+            #[should_panic(expected = "attempt to divide by zero")]
+            fn cannot_create_even_modulus() {
+                let _ = $MontgomeryT::new(10);
+            }
+
+            #[test]
+            #[should_panic(expected = "attempt to divide by zero")]
+            fn cannot_create_zero_modulus() {
+                let _ = $MontgomeryT::new(0);
+            }
+
+            #[test]
+            fn handles_extreme_moduli() {
+                let m = $MontgomeryT::new(1);
+                assert_eq!(m.from_montgomery(m.to_montgomery(<$BaseT>::MAX)), 0);
+                assert_eq!(m.from_montgomery(m.one()), 0);
+
+                let m = $MontgomeryT::new(<$BaseT>::MAX);
+                let x = m.to_montgomery(<$BaseT>::MAX - 1);
+                assert_eq!(m.from_montgomery(m.square(x)), 1);
+            }
+
+            quickcheck! {
+                fn conversion_roundtrips(m: OddModulus, x: $BaseT) -> bool {
+                    let m = $MontgomeryT::new(m.0);
+                    m.from_montgomery(m.to_montgomery(x)) == x % m.get()
+                }
+            }
+
+            quickcheck! {
+                fn agrees_with_modulus(m: OddModulus, a: $BaseT, b: $BaseT) -> bool {
+                    let (montgomery, modulus) = ($MontgomeryT::new(m.0), $ModulusT::new(m.0));
+                    let (ma, mb) = (montgomery.to_montgomery(a), montgomery.to_montgomery(b));
+
+                    montgomery.from_montgomery(montgomery.mul(ma, mb)) == modulus.mul_mod(a, b)
+                        && montgomery.from_montgomery(montgomery.square(ma)) == modulus.mul_mod(a, a)
+                        && montgomery.from_montgomery(montgomery.add(ma, mb)) == modulus.add_mod(a, b)
+                        && montgomery.from_montgomery(montgomery.sub(ma, mb)) == modulus.sub_mod(a, b)
+                }
+            }
+
+            quickcheck! {
+                fn pow_agrees_with_modulus(m: OddModulus, base: $BaseT, exp: $BaseT) -> bool {
+                    let (montgomery, modulus) = ($MontgomeryT::new(m.0), $ModulusT::new(m.0));
+                    let power = montgomery.pow(montgomery.to_montgomery(base), exp);
+                    montgomery.from_montgomery(power) == modulus.pow_mod(base, exp)
+                }
+            }
+        }
+    };
+}
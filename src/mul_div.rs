@@ -0,0 +1,343 @@
+/// Rounding mode used by methods that compute quotients which are not integers.
+///
+/// # Examples
+/// ```
+/// use quickdiv::{DivisorI32, RoundingMode};
+///
+/// let d = DivisorI32::new(4);
+/// assert_eq!(d.mul_div_round(-3, 5, RoundingMode::Floor), Some(-4));
+/// assert_eq!(d.mul_div_round(-3, 5, RoundingMode::Ceil), Some(-3));
+/// assert_eq!(d.mul_div_round(-3, 5, RoundingMode::Trunc), Some(-3));
+/// assert_eq!(d.mul_div_round(-3, 5, RoundingMode::Nearest), Some(-4));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+    /// Round towards zero, like the `/` operator.
+    Trunc,
+    /// Round to the nearest integer, with ties rounded away from zero.
+    Nearest,
+}
+
+macro_rules! unsigned_mul_div_impl {
+    ($SelfT:ident, $BaseT:ty) => {
+        /// Returns `a * b / self`, rounded towards zero, or `None` if the result
+        /// does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// The product is computed in full double-width precision, so it never
+        /// overflows.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(100);")]
+        #[doc = concat!("assert_eq!(d.mul_div(", stringify!($BaseT), "::MAX, 50), Some(", stringify!($BaseT), "::MAX / 2));")]
+        #[doc = concat!("assert_eq!(d.mul_div(", stringify!($BaseT), "::MAX, 101), None);")]
+        /// ```
+        #[inline]
+        pub fn mul_div(&self, a: $BaseT, b: $BaseT) -> Option<$BaseT> {
+            self.mul_div_round(a, b, RoundingMode::Trunc)
+        }
+
+        /// Returns `a * b / self`, rounded according to `mode`, or `None` if the
+        /// result does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// The product is computed in full double-width precision, so it never
+        /// overflows.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::{", stringify!($SelfT), ", RoundingMode};")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(4);")]
+        /// assert_eq!(d.mul_div_round(3, 5, RoundingMode::Floor), Some(3));
+        /// assert_eq!(d.mul_div_round(3, 5, RoundingMode::Ceil), Some(4));
+        /// assert_eq!(d.mul_div_round(3, 5, RoundingMode::Nearest), Some(4));
+        /// ```
+        pub fn mul_div_round(&self, a: $BaseT, b: $BaseT, mode: RoundingMode) -> Option<$BaseT> {
+            let d = self.get();
+            let (hi, lo) = (Self::mulh(a, b), a.wrapping_mul(b));
+            if hi >= d {
+                return None;
+            }
+
+            let (q, r) = Self::div_rem_wide_preinv(hi, lo, d, self.reciprocal);
+            let round_up = match mode {
+                RoundingMode::Floor | RoundingMode::Trunc => false,
+                RoundingMode::Ceil => r != 0,
+                RoundingMode::Nearest => r >= d - r,
+            };
+
+            if round_up {
+                q.checked_add(1)
+            } else {
+                Some(q)
+            }
+        }
+
+        /// Returns the remainder of dividing `a * b` by `self`.
+        ///
+        /// The product is computed in full double-width precision, so it never
+        /// overflows.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(7);")]
+        #[doc = concat!("assert_eq!(d.mul_rem(", stringify!($BaseT), "::MAX, 2), (", stringify!($BaseT), "::MAX % 7) * 2 % 7);")]
+        /// ```
+        #[inline]
+        pub fn mul_rem(&self, a: $BaseT, b: $BaseT) -> $BaseT {
+            let d = self.get();
+            let hi = self.rem_of(Self::mulh(a, b));
+            Self::div_rem_wide_preinv(hi, a.wrapping_mul(b), d, self.reciprocal).1
+        }
+    };
+}
+
+macro_rules! signed_mul_div_impl {
+    ($SelfT:ident, $BaseT:ty, $UnsignedSelfT:ident, $UnsignedBaseT:ty) => {
+        /// Returns `a * b / self`, rounded towards zero, or `None` if the result
+        /// does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// The product is computed in full double-width precision, so it never
+        /// overflows.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(-100);")]
+        #[doc = concat!("assert_eq!(d.mul_div(", stringify!($BaseT), "::MAX, 50), Some(-(", stringify!($BaseT), "::MAX / 2)));")]
+        #[doc = concat!("assert_eq!(d.mul_div(", stringify!($BaseT), "::MAX, 102), None);")]
+        /// ```
+        #[inline]
+        pub fn mul_div(&self, a: $BaseT, b: $BaseT) -> Option<$BaseT> {
+            self.mul_div_round(a, b, RoundingMode::Trunc)
+        }
+
+        /// Returns `a * b / self`, rounded according to `mode`, or `None` if the
+        /// result does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// The product is computed in full double-width precision, so it never
+        /// overflows.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::{", stringify!($SelfT), ", RoundingMode};")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(4);")]
+        /// assert_eq!(d.mul_div_round(-3, 5, RoundingMode::Floor), Some(-4));
+        /// assert_eq!(d.mul_div_round(-3, 5, RoundingMode::Ceil), Some(-3));
+        /// assert_eq!(d.mul_div_round(-3, 5, RoundingMode::Nearest), Some(-4));
+        /// ```
+        #[allow(clippy::cast_possible_wrap)]
+        pub fn mul_div_round(&self, a: $BaseT, b: $BaseT, mode: RoundingMode) -> Option<$BaseT> {
+            let d = self.get();
+            let negative = ((a < 0) != (b < 0)) != (d < 0);
+
+            // Work with magnitudes, fixing up the sign at the end.
+            let (ua, ub, ud) = (a.unsigned_abs(), b.unsigned_abs(), d.unsigned_abs());
+            let (hi, lo) = (<$UnsignedSelfT>::mulh(ua, ub), ua.wrapping_mul(ub));
+            if hi >= ud {
+                return None;
+            }
+
+            let (q, r) = <$UnsignedSelfT>::div_rem_wide_preinv(hi, lo, ud, self.reciprocal);
+            let round_up = match mode {
+                RoundingMode::Trunc => false,
+                RoundingMode::Floor => negative && r != 0,
+                RoundingMode::Ceil => !negative && r != 0,
+                RoundingMode::Nearest => r >= ud - r,
+            };
+            let q = if round_up { q.checked_add(1)? } else { q };
+
+            if negative {
+                if q > <$BaseT>::MIN.unsigned_abs() {
+                    None
+                } else {
+                    Some((q as $BaseT).wrapping_neg())
+                }
+            } else if q > <$BaseT>::MAX as $UnsignedBaseT {
+                None
+            } else {
+                Some(q as $BaseT)
+            }
+        }
+
+        /// Returns the remainder of dividing `a * b` by `self`.
+        ///
+        /// The product is computed in full double-width precision, so it never
+        /// overflows. Like `rem_of`, the result has the same sign as `a * b`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(7);")]
+        #[doc = concat!("assert_eq!(d.mul_rem(", stringify!($BaseT), "::MIN, 1), ", stringify!($BaseT), "::MIN % 7);")]
+        /// assert_eq!(d.mul_rem(-3, 5), -1);
+        /// ```
+        #[allow(clippy::cast_possible_wrap)]
+        pub fn mul_rem(&self, a: $BaseT, b: $BaseT) -> $BaseT {
+            let negative = (a < 0) != (b < 0);
+
+            let (ua, ub, ud) = (a.unsigned_abs(), b.unsigned_abs(), self.get().unsigned_abs());
+            let hi = <$UnsignedSelfT>::mulh(ua, ub);
+            let hi = <$UnsignedSelfT>::div_rem_wide_preinv(0, hi, ud, self.reciprocal).1;
+            let r = <$UnsignedSelfT>::div_rem_wide_preinv(hi, ua.wrapping_mul(ub), ud, self.reciprocal).1;
+
+            if negative {
+                (r as $BaseT).wrapping_neg()
+            } else {
+                r as $BaseT
+            }
+        }
+    };
+}
+
+macro_rules! unsigned_mul_div_tests {
+    ($SelfT:ty, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            const MODES: [RoundingMode; 4] = [
+                RoundingMode::Floor,
+                RoundingMode::Ceil,
+                RoundingMode::Trunc,
+                RoundingMode::Nearest,
+            ];
+
+            reference_wide_impl! { $BaseT }
+
+            quickcheck! {
+                fn mul_div_round_agrees_with_long_division(a: $BaseT, b: $BaseT, d: $SelfT, mode: u8) -> bool {
+                    let mode = MODES[usize::from(mode) % MODES.len()];
+                    let (hi, lo) = reference_mul_wide(a, b);
+
+                    let expected = if hi >= d.get() {
+                        None
+                    } else {
+                        let (q, r) = reference_div_rem_wide(hi, lo, d.get());
+                        let round_up = match mode {
+                            RoundingMode::Floor | RoundingMode::Trunc => false,
+                            RoundingMode::Ceil => r > 0,
+                            RoundingMode::Nearest => r >= d.get() / 2 + d.get() % 2,
+                        };
+                        if round_up { q.checked_add(1) } else { Some(q) }
+                    };
+
+                    d.mul_div_round(a, b, mode) == expected
+                }
+            }
+
+            quickcheck! {
+                fn mul_div_of_small_values_agrees_with_builtin(a: u8, b: u8, d: $SelfT) -> bool {
+                    let (a, b) = (<$BaseT>::from(a) % 16, <$BaseT>::from(b) % 16);
+                    d.mul_div(a, b) == Some(a * b / d.get())
+                }
+            }
+
+            quickcheck! {
+                fn mul_rem_agrees_with_long_division(a: $BaseT, b: $BaseT, d: $SelfT) -> bool {
+                    let (hi, lo) = reference_mul_wide(a, b);
+                    d.mul_rem(a, b) == reference_div_rem_wide(hi % d.get(), lo, d.get()).1
+                }
+            }
+        }
+    };
+}
+
+macro_rules! signed_mul_div_tests {
+    ($SelfT:ty, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        mod $ModName {
+            use core::convert::TryFrom;
+            use quickcheck::*;
+
+            use super::*;
+
+            const MODES: [RoundingMode; 4] = [
+                RoundingMode::Floor,
+                RoundingMode::Ceil,
+                RoundingMode::Trunc,
+                RoundingMode::Nearest,
+            ];
+
+            // Restricts 128-bit values to 64 bits, so that their products fit into an i128.
+            fn narrow(n: $BaseT) -> i128 {
+                if <$BaseT>::BITS > 64 {
+                    i128::from(n as i64)
+                } else {
+                    n as i128
+                }
+            }
+
+            fn reference_mul_div_round(a: i128, b: i128, d: i128, mode: RoundingMode) -> Option<$BaseT> {
+                let p = a * b;
+                let (q, r) = (p / d, p % d);
+                let q = match mode {
+                    RoundingMode::Trunc => q,
+                    RoundingMode::Floor if r != 0 && (r < 0) != (d < 0) => q - 1,
+                    RoundingMode::Ceil if r != 0 && (r < 0) == (d < 0) => q + 1,
+                    RoundingMode::Floor | RoundingMode::Ceil => q,
+                    RoundingMode::Nearest => {
+                        let (up, ud) = (p.unsigned_abs(), d.unsigned_abs());
+                        let q = (up / ud + u128::from(2 * (up % ud) >= ud)) as i128;
+                        if (p < 0) != (d < 0) { -q } else { q }
+                    }
+                };
+                <$BaseT>::try_from(q).ok()
+            }
+
+            quickcheck! {
+                fn mul_div_round_agrees_with_reference(a: $BaseT, b: $BaseT, d: $SelfT, mode: u8) -> bool {
+                    let mode = MODES[usize::from(mode) % MODES.len()];
+                    let (a, b) = (narrow(a), narrow(b));
+                    let result = d.mul_div_round(a as $BaseT, b as $BaseT, mode);
+                    result == reference_mul_div_round(a, b, d.get() as i128, mode)
+                }
+            }
+
+            quickcheck! {
+                fn mul_rem_agrees_with_reference(a: $BaseT, b: $BaseT, d: $SelfT) -> bool {
+                    let (a, b) = (narrow(a), narrow(b));
+                    d.mul_rem(a as $BaseT, b as $BaseT) as i128 == a * b % (d.get() as i128)
+                }
+            }
+
+            #[test]
+            fn mul_div_round_edge_cases() {
+                let values = [$BaseT::MIN, $BaseT::MIN + 1, -1, 0, 1, $BaseT::MAX];
+                for &d in values.iter().filter(|&&d| d != 0) {
+                    let divisor = <$SelfT>::new(d);
+                    for &a in &values {
+                        for &b in &values {
+                            for &mode in &MODES {
+                                let (a, b) = (narrow(a), narrow(b));
+                                assert_eq!(
+                                    divisor.mul_div_round(a as $BaseT, b as $BaseT, mode),
+                                    reference_mul_div_round(a, b, d as i128, mode),
+                                    "{} * {} / {} ({:?})", a, b, d, mode
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,407 @@
+macro_rules! declare_multiples_struct {
+    ($SelfT:ident, $DivisorT:ident, $BaseT:ty, $UnsignedBaseT:ty) => {
+        /// Iterator over the multiples of a
+        #[doc = concat!("[`", stringify!($DivisorT), "`]")]
+        /// within a range, in increasing order.
+        ///
+        /// This struct is created by
+        #[doc = concat!("[`", stringify!($DivisorT), "::multiples_in`].")]
+        #[derive(Clone, Debug)]
+        pub struct $SelfT {
+            next: $BaseT,
+            step: $UnsignedBaseT,
+            remaining: $UnsignedBaseT,
+        }
+
+        impl Iterator for $SelfT {
+            type Item = $BaseT;
+
+            #[inline]
+            #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+            fn next(&mut self) -> Option<$BaseT> {
+                if self.remaining == 0 {
+                    return None;
+                }
+
+                let multiple = self.next;
+                // Stepping past the last multiple may wrap around, but that
+                // value is never returned.
+                self.next = (self.next as $UnsignedBaseT).wrapping_add(self.step) as $BaseT;
+                self.remaining -= 1;
+                Some(multiple)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                use core::convert::TryFrom;
+
+                match usize::try_from(self.remaining) {
+                    Ok(remaining) => (remaining, Some(remaining)),
+                    Err(_) => (usize::MAX, None),
+                }
+            }
+        }
+
+        impl core::iter::FusedIterator for $SelfT {}
+    };
+}
+
+macro_rules! unsigned_multiples_impl {
+    ($SelfT:ident, $MultiplesT:ident, $BaseT:ty) => {
+        /// Returns the largest multiple of `self` that is less than or equal to
+        /// `n`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(15);")]
+        /// assert_eq!(d.floor_to_multiple(100), 90);
+        /// assert_eq!(d.floor_to_multiple(90), 90);
+        /// ```
+        #[inline]
+        pub const fn floor_to_multiple(&self, n: $BaseT) -> $BaseT {
+            n - self.rem_of(n)
+        }
+
+        /// Returns the smallest multiple of `self` that is greater than or equal
+        /// to `n`, or `None` if it does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(10);")]
+        /// assert_eq!(d.ceil_to_multiple(95), Some(100));
+        /// assert_eq!(d.ceil_to_multiple(90), Some(90));
+        #[doc = concat!("assert_eq!(d.ceil_to_multiple(", stringify!($BaseT), "::MAX), None);")]
+        /// ```
+        #[inline]
+        pub const fn ceil_to_multiple(&self, n: $BaseT) -> Option<$BaseT> {
+            let r = self.rem_of(n);
+            if r == 0 {
+                Some(n)
+            } else {
+                n.checked_add(self.get() - r)
+            }
+        }
+
+        /// Returns the smallest multiple of `self` that is strictly greater than
+        /// `n`, or `None` if it does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(15);")]
+        /// assert_eq!(d.next_multiple_after(100), Some(105));
+        /// assert_eq!(d.next_multiple_after(90), Some(105));
+        /// ```
+        #[inline]
+        pub const fn next_multiple_after(&self, n: $BaseT) -> Option<$BaseT> {
+            n.checked_add(self.get() - self.rem_of(n))
+        }
+
+        /// Returns the number of multiples of `self` within `range`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(15);")]
+        /// assert_eq!(d.count_multiples_in(0..100), 7);
+        /// assert_eq!(d.count_multiples_in(16..30), 0);
+        /// ```
+        pub fn count_multiples_in(&self, range: core::ops::Range<$BaseT>) -> $BaseT {
+            if range.start >= range.end {
+                return 0;
+            }
+
+            let first_is_multiple = self.divides(range.start) as $BaseT;
+            self.div_of(range.end - 1) - self.div_of(range.start) + first_is_multiple
+        }
+
+        /// Returns an iterator over the multiples of `self` within `range`, in
+        /// increasing order.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(15);")]
+        /// assert!(d.multiples_in(20..70).eq([30, 45, 60]));
+        /// ```
+        pub fn multiples_in(&self, range: core::ops::Range<$BaseT>) -> $MultiplesT {
+            let remaining = self.count_multiples_in(range.clone());
+            $MultiplesT {
+                next: self.ceil_to_multiple(range.start).unwrap_or(0),
+                step: self.get(),
+                remaining,
+            }
+        }
+    };
+}
+
+macro_rules! signed_multiples_impl {
+    ($SelfT:ident, $MultiplesT:ident, $BaseT:ty, $UnsignedBaseT:ty) => {
+        /// Returns the largest multiple of `self` that is less than or equal to
+        /// `n`, or `None` if it does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// This rounds towards negative infinity, i.e. follows floor division,
+        /// regardless of the sign of the divisor.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(-15);")]
+        /// assert_eq!(d.floor_to_multiple(100), Some(90));
+        /// assert_eq!(d.floor_to_multiple(-100), Some(-105));
+        #[doc = concat!("assert_eq!(d.floor_to_multiple(", stringify!($BaseT), "::MIN + 1), None);")]
+        /// ```
+        #[inline]
+        #[allow(clippy::cast_possible_wrap)]
+        pub const fn floor_to_multiple(&self, n: $BaseT) -> Option<$BaseT> {
+            n.checked_sub(self.rem_euclid_of(n) as $BaseT)
+        }
+
+        /// Returns the smallest multiple of `self` that is greater than or equal
+        /// to `n`, or `None` if it does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(15);")]
+        /// assert_eq!(d.ceil_to_multiple(100), Some(105));
+        /// assert_eq!(d.ceil_to_multiple(-100), Some(-90));
+        #[doc = concat!("assert_eq!(d.ceil_to_multiple(", stringify!($BaseT), "::MAX - 1), None);")]
+        /// ```
+        #[inline]
+        pub const fn ceil_to_multiple(&self, n: $BaseT) -> Option<$BaseT> {
+            let r = self.rem_euclid_of(n);
+            if r == 0 {
+                Some(n)
+            } else {
+                Self::checked_step(n, self.get().unsigned_abs() - r)
+            }
+        }
+
+        /// Returns the smallest multiple of `self` that is strictly greater than
+        /// `n`, or `None` if it does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(15);")]
+        /// assert_eq!(d.next_multiple_after(-100), Some(-90));
+        /// assert_eq!(d.next_multiple_after(-90), Some(-75));
+        /// ```
+        #[inline]
+        pub const fn next_multiple_after(&self, n: $BaseT) -> Option<$BaseT> {
+            Self::checked_step(n, self.get().unsigned_abs() - self.rem_euclid_of(n))
+        }
+
+        /// Returns the number of multiples of `self` within `range`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(15);")]
+        /// assert_eq!(d.count_multiples_in(-50..50), 7);
+        /// assert_eq!(d.count_multiples_in(-29..-15), 0);
+        /// ```
+        #[allow(clippy::cast_sign_loss)]
+        pub fn count_multiples_in(&self, range: core::ops::Range<$BaseT>) -> $UnsignedBaseT {
+            if range.start >= range.end {
+                return 0;
+            }
+
+            let first_is_multiple = self.divides(range.start) as $UnsignedBaseT;
+            let last = self.floor_div_abs(range.end - 1) as $UnsignedBaseT;
+            let first = self.floor_div_abs(range.start) as $UnsignedBaseT;
+            last.wrapping_sub(first) + first_is_multiple
+        }
+
+        /// Returns an iterator over the multiples of `self` within `range`, in
+        /// increasing order.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(-15);")]
+        /// assert!(d.multiples_in(-20..20).eq([-15, 0, 15]));
+        /// ```
+        pub fn multiples_in(&self, range: core::ops::Range<$BaseT>) -> $MultiplesT {
+            let remaining = self.count_multiples_in(range.clone());
+            $MultiplesT {
+                next: self.ceil_to_multiple(range.start).unwrap_or(0),
+                step: self.get().unsigned_abs(),
+                remaining,
+            }
+        }
+
+        // Returns n modulo the absolute value of the divisor, which is always
+        // non-negative.
+        #[inline]
+        #[allow(clippy::cast_sign_loss)]
+        const fn rem_euclid_of(&self, n: $BaseT) -> $UnsignedBaseT {
+            let r = self.rem_of(n);
+            if r < 0 {
+                (r as $UnsignedBaseT).wrapping_add(self.get().unsigned_abs())
+            } else {
+                r as $UnsignedBaseT
+            }
+        }
+
+        // Returns n divided by the absolute value of the divisor, rounded
+        // towards negative infinity.
+        #[inline]
+        const fn floor_div_abs(&self, n: $BaseT) -> $BaseT {
+            let q = self.div_of(n);
+            let q = if self.get() < 0 { q.wrapping_neg() } else { q };
+            if self.rem_of(n) < 0 {
+                q - 1
+            } else {
+                q
+            }
+        }
+
+        // Returns n + step for a positive step, or None if it overflows.
+        #[inline]
+        #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        const fn checked_step(n: $BaseT, step: $UnsignedBaseT) -> Option<$BaseT> {
+            let sum = (n as $UnsignedBaseT).wrapping_add(step) as $BaseT;
+            if sum > n {
+                Some(sum)
+            } else {
+                None
+            }
+        }
+    };
+}
+
+macro_rules! unsigned_multiples_tests {
+    ($DivisorT:ident, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_lossless, clippy::cast_possible_truncation)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            #[test]
+            #[allow(clippy::reversed_empty_ranges)]
+            fn counts_full_range() {
+                let d = $DivisorT::new(1);
+                assert_eq!(d.count_multiples_in(0..<$BaseT>::MAX), <$BaseT>::MAX);
+                assert_eq!(d.count_multiples_in(5..5), 0);
+                assert_eq!(d.count_multiples_in(6..5), 0);
+            }
+
+            #[test]
+            fn iterates_up_to_max() {
+                let d = $DivisorT::new(<$BaseT>::MAX);
+                assert!(d.multiples_in(1..<$BaseT>::MAX).eq([]));
+                assert!(d.multiples_in(0..<$BaseT>::MAX).eq([0]));
+                assert_eq!(d.next_multiple_after(0), Some(<$BaseT>::MAX));
+                assert_eq!(d.next_multiple_after(<$BaseT>::MAX), None);
+            }
+
+            quickcheck! {
+                fn rounds_to_multiples(d: $BaseT, n: $BaseT) -> bool {
+                    let d = d.max(1);
+                    let divisor = $DivisorT::new(d);
+                    let floor = n / d * d;
+                    let ceil = if n % d == 0 { Some(n) } else { floor.checked_add(d) };
+
+                    divisor.floor_to_multiple(n) == floor
+                        && divisor.ceil_to_multiple(n) == ceil
+                        && divisor.next_multiple_after(n) == floor.checked_add(d)
+                }
+            }
+
+            quickcheck! {
+                fn iterates_multiples_in_range(d: u8, start: $BaseT, len: u8) -> bool {
+                    let divisor = $DivisorT::new(d.max(1) as $BaseT);
+                    let end = start.saturating_add(len as $BaseT);
+                    let count = (start..end).filter(|&n| divisor.divides(n)).count();
+
+                    divisor.count_multiples_in(start..end) as usize == count
+                        && divisor.multiples_in(start..end).count() == count
+                        && divisor.multiples_in(start..end).eq((start..end).filter(|&n| divisor.divides(n)))
+                }
+            }
+        }
+    };
+}
+
+macro_rules! signed_multiples_tests {
+    ($DivisorT:ident, $BaseT:ident, $UnsignedBaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            #[test]
+            #[allow(clippy::reversed_empty_ranges)]
+            fn counts_full_range() {
+                let d = $DivisorT::new(-1);
+                assert_eq!(d.count_multiples_in(<$BaseT>::MIN..<$BaseT>::MAX), <$UnsignedBaseT>::MAX);
+                assert_eq!(d.count_multiples_in(-5..-5), 0);
+                assert_eq!(d.count_multiples_in(-4..-5), 0);
+            }
+
+            #[test]
+            fn handles_min_divisor() {
+                let d = $DivisorT::new(<$BaseT>::MIN);
+                assert_eq!(d.floor_to_multiple(-1), Some(<$BaseT>::MIN));
+                assert_eq!(d.ceil_to_multiple(-1), Some(0));
+                assert_eq!(d.next_multiple_after(0), None);
+                assert!(d.multiples_in(<$BaseT>::MIN..<$BaseT>::MAX).eq([<$BaseT>::MIN, 0]));
+            }
+
+            quickcheck! {
+                fn rounds_to_multiples(d: $BaseT, n: $BaseT) -> bool {
+                    let d = if d == 0 { 1 } else { d };
+                    let divisor = $DivisorT::new(d);
+                    let r = n.wrapping_rem_euclid(d);
+                    let floor = n.checked_sub(r);
+                    let largest = <$BaseT>::MAX - <$BaseT>::MAX.wrapping_rem_euclid(d);
+
+                    let ceil_ok = match divisor.ceil_to_multiple(n) {
+                        Some(m) => m.wrapping_rem(d) == 0 && m >= n && (m as $UnsignedBaseT).wrapping_sub(n as $UnsignedBaseT) < d.unsigned_abs(),
+                        None => n > largest,
+                    };
+                    let next_ok = match divisor.next_multiple_after(n) {
+                        Some(m) => m.wrapping_rem(d) == 0 && m > n && (m as $UnsignedBaseT).wrapping_sub(n as $UnsignedBaseT) <= d.unsigned_abs(),
+                        None => n >= largest,
+                    };
+
+                    divisor.floor_to_multiple(n) == floor && ceil_ok && next_ok
+                }
+            }
+
+            quickcheck! {
+                fn iterates_multiples_in_range(d: i8, start: $BaseT, len: u8) -> bool {
+                    let divisor = $DivisorT::new(if d == 0 { 1 } else { d as $BaseT });
+                    let end = start.saturating_add(len as $BaseT);
+                    let count = (start..end).filter(|&n| divisor.divides(n)).count();
+
+                    divisor.count_multiples_in(start..end) as usize == count
+                        && divisor.multiples_in(start..end).eq((start..end).filter(|&n| divisor.divides(n)))
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,559 @@
+/// Strategy used by an adaptive divisor to carry out division and modulo
+/// operations.
+///
+/// Dividing by a precomputed divisor replaces the hardware division instruction
+/// with a short sequence of multiplications, additions and shifts. On most CPUs
+/// this is considerably faster, but some newer processors (e.g. the Apple M1/M2)
+/// have very fast hardware division, in which case the speed-up is much smaller
+/// or even negative.
+///
+/// # Examples
+/// ```
+/// use quickdiv::{AdaptiveDivisorU64, DivisionPolicy};
+///
+/// let d = AdaptiveDivisorU64::with_policy(7, DivisionPolicy::Hardware);
+/// assert_eq!(d.policy(), DivisionPolicy::Hardware);
+/// assert_eq!(100 / d, 14);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DivisionPolicy {
+    /// Always use the precomputed sequence of multiplications and shifts.
+    Magic,
+    /// Always use the built-in hardware division instruction.
+    Hardware,
+    /// Use whichever of `Magic` or `Hardware` is currently preferred for the
+    /// divisor type. This is `Magic` unless changed using `set_auto_policy` or
+    /// by running the calibration routine (both available with the `std`
+    /// feature).
+    Auto,
+}
+
+/// Times division by both strategies for every adaptive divisor type and sets
+/// the preferred policy for each of them to the faster one.
+///
+/// This takes a few milliseconds, and should be run at most once, early on in
+/// the program. Divisors created before calibration keep their strategy.
+///
+/// # Examples
+/// ```
+/// use quickdiv::{AdaptiveDivisorU64, DivisionPolicy};
+///
+/// quickdiv::calibrate();
+/// let d = AdaptiveDivisorU64::new(7);
+/// assert_eq!(d.policy(), AdaptiveDivisorU64::auto_policy());
+/// assert_eq!(100 / d, 14);
+/// ```
+#[cfg(feature = "std")]
+pub fn calibrate() {
+    crate::AdaptiveDivisorU8::calibrate();
+    crate::AdaptiveDivisorU16::calibrate();
+    crate::AdaptiveDivisorU32::calibrate();
+    crate::AdaptiveDivisorU64::calibrate();
+    crate::AdaptiveDivisorU128::calibrate();
+    crate::AdaptiveDivisorUsize::calibrate();
+    crate::AdaptiveDivisorI8::calibrate();
+    crate::AdaptiveDivisorI16::calibrate();
+    crate::AdaptiveDivisorI32::calibrate();
+    crate::AdaptiveDivisorI64::calibrate();
+    crate::AdaptiveDivisorI128::calibrate();
+    crate::AdaptiveDivisorIsize::calibrate();
+}
+
+// Hides a value from the optimizer during calibration. std::hint::black_box
+// would be the natural choice, but is only stable since Rust 1.66.
+#[cfg(feature = "std")]
+#[inline(never)]
+pub(crate) fn opaque<T>(value: T) -> T {
+    value
+}
+
+macro_rules! declare_adaptive_struct {
+    ($SelfT:ident, $InnerT:ident, $DivisorT:ident, $BaseT:ty, $SIZE:literal) => {
+        /// Divisor for division and modulo operations by
+        #[doc = concat!($SIZE)]
+        /// integer values, which either uses a precomputed
+        #[doc = concat!("[`", stringify!($DivisorT), "`]")]
+        /// or falls back to the hardware division instruction, according to a
+        /// [`DivisionPolicy`].
+        ///
+        /// Choosing between the two strategies costs a branch on every
+        /// operation, which is why
+        #[doc = concat!("[`", stringify!($DivisorT), "`]")]
+        /// itself always uses the precomputed one.
+        ///
+        /// Besides division and modulo, it provides `div_exact`, `mul_div`, and
+        /// the slice and multiples helpers. The remaining methods, such as
+        /// splitting or digit extraction, are available on the precomputed
+        /// divisor returned by `to_divisor`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::{", stringify!($SelfT), ", DivisionPolicy};")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::with_policy(7, DivisionPolicy::Hardware);")]
+        /// assert_eq!(100 / d, 14);
+        /// assert_eq!(100 % d, 2);
+        /// ```
+        #[derive(Clone, Copy)]
+        pub struct $SelfT {
+            inner: $InnerT,
+        }
+
+        #[derive(Clone, Copy)]
+        enum $InnerT {
+            Magic($DivisorT),
+            Hardware($BaseT),
+        }
+
+        impl $SelfT {
+            policy_impl! { $SelfT, $InnerT, $DivisorT, $BaseT }
+            slice_impl! { $SelfT, $BaseT }
+        }
+
+        impl From<$DivisorT> for $SelfT {
+            #[inline]
+            fn from(d: $DivisorT) -> $SelfT {
+                $SelfT {
+                    inner: $InnerT::Magic(d),
+                }
+            }
+        }
+
+        impl_traits! { $SelfT, $BaseT }
+    };
+}
+
+macro_rules! policy_impl {
+    ($SelfT:ident, $InnerT:ident, $DivisorT:ident, $BaseT:ty) => {
+        /// Creates a divisor for `d` that uses the strategy currently preferred
+        /// for this type, as returned by `auto_policy`.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `d` equals zero.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(9);")]
+        /// assert_eq!(d.rem_of(31), 4);
+        /// ```
+        pub fn new(d: $BaseT) -> $SelfT {
+            Self::with_policy(d, DivisionPolicy::Auto)
+        }
+
+        /// Creates a divisor for `d` that carries out division and modulo
+        /// operations according to `policy`.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `d` equals zero.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::{", stringify!($SelfT), ", DivisionPolicy};")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::with_policy(9, DivisionPolicy::Hardware);")]
+        /// assert_eq!(d.rem_of(31), 4);
+        /// ```
+        pub fn with_policy(d: $BaseT, policy: DivisionPolicy) -> $SelfT {
+            let policy = match policy {
+                DivisionPolicy::Auto => Self::auto_policy(),
+                policy => policy,
+            };
+
+            let inner = match policy {
+                DivisionPolicy::Hardware => {
+                    // Forces a panic when d = 0, just like the precomputed divisor.
+                    let _ = 1 / d;
+                    $InnerT::Hardware(d)
+                }
+                _ => $InnerT::Magic(<$DivisorT>::new(d)),
+            };
+            $SelfT { inner }
+        }
+
+        /// Returns the strategy this divisor uses, either `DivisionPolicy::Magic`
+        /// or `DivisionPolicy::Hardware`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::{", stringify!($SelfT), ", DivisionPolicy};")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::with_policy(9, DivisionPolicy::Magic);")]
+        /// assert_eq!(d.policy(), DivisionPolicy::Magic);
+        /// ```
+        #[inline]
+        pub const fn policy(&self) -> DivisionPolicy {
+            match self.inner {
+                $InnerT::Magic(_) => DivisionPolicy::Magic,
+                $InnerT::Hardware(_) => DivisionPolicy::Hardware,
+            }
+        }
+
+        /// Returns the value that was used to construct this divisor as a
+        /// primitive type.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(7);")]
+        /// assert_eq!(d.get(), 7);
+        /// ```
+        #[inline]
+        pub const fn get(&self) -> $BaseT {
+            match self.inner {
+                $InnerT::Magic(divisor) => divisor.get(),
+                $InnerT::Hardware(d) => d,
+            }
+        }
+
+        /// Returns `true` if `n` is divisible by `self`.
+        ///
+        /// We take `0` to be divisible by all non-zero numbers.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(17);")]
+        /// assert!(d.divides(34));
+        /// ```
+        #[inline]
+        pub const fn divides(&self, n: $BaseT) -> bool {
+            self.rem_of(n) == 0
+        }
+
+        /// Returns the remainder of dividing `n` by `self`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(11);")]
+        /// let rem = d.rem_of(30);
+        /// assert_eq!(rem, 8);
+        /// ```
+        #[inline]
+        pub const fn rem_of(&self, n: $BaseT) -> $BaseT {
+            match self.inner {
+                $InnerT::Magic(divisor) => divisor.rem_of(n),
+                $InnerT::Hardware(d) => n.wrapping_rem(d),
+            }
+        }
+
+        /// Returns the result of dividing `n` by `self`.
+        ///
+        /// Overflow is handled in the same way as by
+        #[doc = concat!("[`", stringify!($DivisorT), "::div_of`].")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(17);")]
+        /// let div = d.div_of(34);
+        /// assert_eq!(div, 2);
+        /// ```
+        #[inline]
+        pub const fn div_of(&self, n: $BaseT) -> $BaseT {
+            match self.inner {
+                $InnerT::Magic(divisor) => divisor.div_of(n),
+                $InnerT::Hardware(d) => n.wrapping_div(d),
+            }
+        }
+
+        /// Returns the result of dividing `n` by `self`, provided that `n` is a
+        /// multiple of `self`.
+        ///
+        /// If `n` is not a multiple of `self`, the result is unspecified.
+        ///
+        /// # Panics
+        ///
+        /// Panics in debug builds if `n` is not divisible by `self`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(12);")]
+        /// assert_eq!(d.div_exact(108), 9);
+        /// ```
+        #[inline]
+        #[allow(clippy::cast_lossless)]
+        pub const fn div_exact(&self, n: $BaseT) -> $BaseT {
+            match self.inner {
+                $InnerT::Magic(divisor) => divisor.div_exact(n),
+                $InnerT::Hardware(d) => {
+                    // Forces a panic in debug builds when n is not a multiple
+                    // of d, since we cannot use panic! in const.
+                    #[cfg(debug_assertions)]
+                    let _ = 1 / (self.divides(n) as $BaseT);
+
+                    n.wrapping_div(d)
+                }
+            }
+        }
+
+        /// Returns `a * b / self`, rounded towards zero, or `None` if the result
+        /// does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// The double-width division always uses the precomputed divisor, which
+        /// is built on every call for `DivisionPolicy::Hardware`. See
+        #[doc = concat!("[`", stringify!($DivisorT), "::mul_div`].")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(4);")]
+        /// assert_eq!(d.mul_div(3, 5), Some(3));
+        /// ```
+        #[inline]
+        pub fn mul_div(&self, a: $BaseT, b: $BaseT) -> Option<$BaseT> {
+            self.to_divisor().mul_div(a, b)
+        }
+
+        /// Returns `a * b / self`, rounded according to `mode`, or `None` if the
+        /// result does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// Like [`mul_div`](Self::mul_div), this always uses the precomputed
+        /// divisor.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::{", stringify!($SelfT), ", RoundingMode};")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(4);")]
+        /// assert_eq!(d.mul_div_round(3, 5, RoundingMode::Ceil), Some(4));
+        /// ```
+        #[inline]
+        pub fn mul_div_round(&self, a: $BaseT, b: $BaseT, mode: RoundingMode) -> Option<$BaseT> {
+            self.to_divisor().mul_div_round(a, b, mode)
+        }
+
+        /// Returns `a * b % self`, computed without overflow.
+        ///
+        /// Like [`mul_div`](Self::mul_div), this always uses the precomputed
+        /// divisor.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(4);")]
+        /// assert_eq!(d.mul_rem(3, 5), 3);
+        /// ```
+        #[inline]
+        pub fn mul_rem(&self, a: $BaseT, b: $BaseT) -> $BaseT {
+            self.to_divisor().mul_rem(a, b)
+        }
+
+        /// Converts this divisor into the equivalent precomputed divisor.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::{", stringify!($SelfT), ", ", stringify!($DivisorT), ", DivisionPolicy};")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::with_policy(9, DivisionPolicy::Hardware);")]
+        #[doc = concat!("assert_eq!(d.to_divisor(), ", stringify!($DivisorT), "::new(9));")]
+        /// ```
+        #[inline]
+        pub const fn to_divisor(self) -> $DivisorT {
+            match self.inner {
+                $InnerT::Magic(divisor) => divisor,
+                $InnerT::Hardware(d) => <$DivisorT>::new(d),
+            }
+        }
+
+        /// Returns the strategy that divisors created with `DivisionPolicy::Auto`
+        /// currently use.
+        ///
+        /// Without the `std` feature, this is always `DivisionPolicy::Magic`.
+        #[cfg(feature = "std")]
+        pub fn auto_policy() -> DivisionPolicy {
+            if Self::prefers_hardware().load(std::sync::atomic::Ordering::Relaxed) {
+                DivisionPolicy::Hardware
+            } else {
+                DivisionPolicy::Magic
+            }
+        }
+
+        /// Returns the strategy that divisors created with `DivisionPolicy::Auto`
+        /// currently use.
+        ///
+        /// Without the `std` feature, this is always `DivisionPolicy::Magic`.
+        #[cfg(not(feature = "std"))]
+        pub fn auto_policy() -> DivisionPolicy {
+            DivisionPolicy::Magic
+        }
+
+        /// Sets the strategy that divisors created with `DivisionPolicy::Auto`
+        /// will use from now on. Passing `DivisionPolicy::Auto` restores the
+        /// default, `DivisionPolicy::Magic`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::{", stringify!($SelfT), ", DivisionPolicy};")]
+        ///
+        #[doc = concat!(stringify!($SelfT), "::set_auto_policy(DivisionPolicy::Hardware);")]
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(9);")]
+        /// assert_eq!(d.policy(), DivisionPolicy::Hardware);
+        /// ```
+        #[cfg(feature = "std")]
+        pub fn set_auto_policy(policy: DivisionPolicy) {
+            let hardware = policy == DivisionPolicy::Hardware;
+            Self::prefers_hardware().store(hardware, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        /// Times division by both strategies and sets the strategy used by
+        /// divisors created with `DivisionPolicy::Auto` to the faster one, which
+        /// is then returned.
+        #[cfg(feature = "std")]
+        pub fn calibrate() -> DivisionPolicy {
+            const ROUNDS: usize = 5;
+
+            // Hide the divisor from the compiler, so that it cannot specialize
+            // the hardware division for a known value.
+            let d: $BaseT = crate::policy::opaque(7);
+            let magic = Self::with_policy(d, DivisionPolicy::Magic);
+            let hardware = Self::with_policy(d, DivisionPolicy::Hardware);
+
+            let mut magic_time = core::time::Duration::MAX;
+            let mut hardware_time = core::time::Duration::MAX;
+            for _ in 0..ROUNDS {
+                magic_time = magic_time.min(Self::time_division(magic));
+                hardware_time = hardware_time.min(Self::time_division(hardware));
+            }
+
+            let policy = if hardware_time < magic_time {
+                DivisionPolicy::Hardware
+            } else {
+                DivisionPolicy::Magic
+            };
+            Self::set_auto_policy(policy);
+            policy
+        }
+
+        #[cfg(feature = "std")]
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        fn time_division(divisor: $SelfT) -> core::time::Duration {
+            const ITERATIONS: usize = 1 << 14;
+            const STEP: $BaseT = 0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C835_u128 as $BaseT;
+
+            let start = std::time::Instant::now();
+            let mut n: $BaseT = 0;
+            for _ in 0..ITERATIONS {
+                n = n.wrapping_add(STEP);
+                crate::policy::opaque(divisor.div_of(crate::policy::opaque(n)));
+            }
+            start.elapsed()
+        }
+
+        // Only kept with the `std` feature, so that targets without atomics
+        // can still use the other policies.
+        #[cfg(feature = "std")]
+        fn prefers_hardware() -> &'static std::sync::atomic::AtomicBool {
+            static PREFERS_HARDWARE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+            &PREFERS_HARDWARE
+        }
+    };
+}
+
+macro_rules! policy_tests {
+    ($SelfT:ident, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            impl Arbitrary for $SelfT {
+                fn arbitrary(g: &mut Gen) -> $SelfT {
+                    let mut d = $BaseT::arbitrary(g);
+                    d = if d == 0 { 1 } else { d };
+
+                    if bool::arbitrary(g) {
+                        <$SelfT>::with_policy(d, DivisionPolicy::Hardware)
+                    } else {
+                        <$SelfT>::with_policy(d, DivisionPolicy::Magic)
+                    }
+                }
+            }
+
+            #[test]
+            #[should_panic(expected = "attempt to divide by zero")]
+            fn cannot_create_zero_hardware_divisors() {
+                let _ = <$SelfT>::with_policy(0, DivisionPolicy::Hardware);
+            }
+
+            // Forcing and calibrating the policy share a global preference, so
+            // they are checked in a single test rather than in parallel ones.
+            #[test]
+            fn auto_policy_can_be_forced_and_calibrated() {
+                assert_eq!(<$SelfT>::auto_policy(), DivisionPolicy::Magic);
+                assert_eq!(<$SelfT>::new(3).policy(), DivisionPolicy::Magic);
+
+                #[cfg(feature = "std")]
+                {
+                    <$SelfT>::set_auto_policy(DivisionPolicy::Hardware);
+                    assert_eq!(<$SelfT>::auto_policy(), DivisionPolicy::Hardware);
+                    assert_eq!(<$SelfT>::new(3).policy(), DivisionPolicy::Hardware);
+
+                    <$SelfT>::set_auto_policy(DivisionPolicy::Auto);
+                    assert_eq!(<$SelfT>::auto_policy(), DivisionPolicy::Magic);
+
+                    let policy = <$SelfT>::calibrate();
+                    assert_ne!(policy, DivisionPolicy::Auto);
+                    assert_eq!(<$SelfT>::auto_policy(), policy);
+                    assert_eq!(<$SelfT>::new(3).policy(), policy);
+                    <$SelfT>::set_auto_policy(DivisionPolicy::Auto);
+                }
+            }
+
+            quickcheck! {
+                fn agrees_with_builtin(x: $BaseT, d: $SelfT) -> bool {
+                    x / d == x.wrapping_div(d.get())
+                        && x % d == x.wrapping_rem(d.get())
+                        && d.divides(x) == (x.wrapping_rem(d.get()) == 0)
+                }
+            }
+
+            quickcheck! {
+                fn policies_agree(x: $BaseT, d: $BaseT) -> bool {
+                    let d = if d == 0 { 1 } else { d };
+                    let magic = <$SelfT>::with_policy(d, DivisionPolicy::Magic);
+                    let hardware = <$SelfT>::with_policy(d, DivisionPolicy::Hardware);
+                    magic == hardware
+                        && magic.to_divisor() == hardware.to_divisor()
+                        && x / magic == x / hardware
+                        && x % magic == x % hardware
+                }
+            }
+
+            quickcheck! {
+                fn forwarded_methods_agree_with_divisor(a: $BaseT, b: $BaseT, d: $SelfT) -> bool {
+                    let divisor = d.to_divisor();
+                    let multiple = a.wrapping_sub(a.wrapping_rem(d.get()));
+                    let range = a.min(b)..a.max(b);
+                    let ns = [a, b, multiple];
+
+                    d.div_exact(multiple) == divisor.div_exact(multiple)
+                        && d.count_divisible(&ns) == divisor.count_divisible(&ns)
+                        && d.mul_div(a, b) == divisor.mul_div(a, b)
+                        && d.mul_div_round(a, b, RoundingMode::Floor)
+                            == divisor.mul_div_round(a, b, RoundingMode::Floor)
+                        && d.mul_rem(a, b) == divisor.mul_rem(a, b)
+                        && d.floor_to_multiple(a) == divisor.floor_to_multiple(a)
+                        && d.ceil_to_multiple(a) == divisor.ceil_to_multiple(a)
+                        && d.next_multiple_after(a) == divisor.next_multiple_after(a)
+                        && d.count_multiples_in(range.clone()) == divisor.count_multiples_in(range)
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,273 @@
+use crate::DivisorU64;
+
+// The smallest prime above 1.5 * 2^k for every k from 5 to 63, so that each
+// prime lies roughly halfway between consecutive powers of two and about
+// doubles the previous one.
+const PRIMES: [DivisorU64; 59] = [
+    DivisorU64::new(53),
+    DivisorU64::new(97),
+    DivisorU64::new(193),
+    DivisorU64::new(389),
+    DivisorU64::new(769),
+    DivisorU64::new(1_543),
+    DivisorU64::new(3_079),
+    DivisorU64::new(6_151),
+    DivisorU64::new(12_289),
+    DivisorU64::new(24_593),
+    DivisorU64::new(49_157),
+    DivisorU64::new(98_317),
+    DivisorU64::new(196_613),
+    DivisorU64::new(393_241),
+    DivisorU64::new(786_433),
+    DivisorU64::new(1_572_869),
+    DivisorU64::new(3_145_739),
+    DivisorU64::new(6_291_469),
+    DivisorU64::new(12_582_917),
+    DivisorU64::new(25_165_843),
+    DivisorU64::new(50_331_653),
+    DivisorU64::new(100_663_319),
+    DivisorU64::new(201_326_611),
+    DivisorU64::new(402_653_189),
+    DivisorU64::new(805_306_457),
+    DivisorU64::new(1_610_612_741),
+    DivisorU64::new(3_221_225_473),
+    DivisorU64::new(6_442_450_967),
+    DivisorU64::new(12_884_901_893),
+    DivisorU64::new(25_769_803_799),
+    DivisorU64::new(51_539_607_599),
+    DivisorU64::new(103_079_215_111),
+    DivisorU64::new(206_158_430_209),
+    DivisorU64::new(412_316_860_441),
+    DivisorU64::new(824_633_720_837),
+    DivisorU64::new(1_649_267_441_681),
+    DivisorU64::new(3_298_534_883_417),
+    DivisorU64::new(6_597_069_766_657),
+    DivisorU64::new(13_194_139_533_349),
+    DivisorU64::new(26_388_279_066_671),
+    DivisorU64::new(52_776_558_133_303),
+    DivisorU64::new(105_553_116_266_509),
+    DivisorU64::new(211_106_232_533_047),
+    DivisorU64::new(422_212_465_066_001),
+    DivisorU64::new(844_424_930_132_057),
+    DivisorU64::new(1_688_849_860_263_953),
+    DivisorU64::new(3_377_699_720_527_897),
+    DivisorU64::new(6_755_399_441_055_827),
+    DivisorU64::new(13_510_798_882_111_519),
+    DivisorU64::new(27_021_597_764_223_071),
+    DivisorU64::new(54_043_195_528_445_957),
+    DivisorU64::new(108_086_391_056_891_941),
+    DivisorU64::new(216_172_782_113_783_843),
+    DivisorU64::new(432_345_564_227_567_621),
+    DivisorU64::new(864_691_128_455_135_281),
+    DivisorU64::new(1_729_382_256_910_270_481),
+    DivisorU64::new(3_458_764_513_820_540_933),
+    DivisorU64::new(6_917_529_027_641_081_903),
+    DivisorU64::new(13_835_058_055_282_163_729),
+];
+
+/// Maps hashes to buckets of an open-addressing hash table whose capacity is
+/// always a prime, taken from a fixed table of primes that roughly double.
+///
+/// The divisors for all primes in the table are computed at compile time, so
+/// growing or shrinking the table never needs to compute a new divisor, and
+/// reducing a hash takes a multiplication and a shift instead of a hardware
+/// division.
+///
+/// # Examples
+/// ```
+/// use quickdiv::PrimeBucketIndexer;
+///
+/// let mut indexer = PrimeBucketIndexer::new();
+/// assert_eq!(indexer.capacity(), 53);
+/// assert_eq!(indexer.index(1000), 1000 % 53);
+///
+/// assert!(indexer.grow());
+/// assert_eq!(indexer.capacity(), 97);
+/// assert_eq!(indexer.index(1000), 1000 % 97);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PrimeBucketIndexer {
+    level: usize,
+}
+
+impl PrimeBucketIndexer {
+    /// Creates an indexer with the smallest capacity in the table.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { level: 0 }
+    }
+
+    /// Creates an indexer with the smallest capacity in the table that is at
+    /// least `min_capacity`, or returns `None` if there is no such capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickdiv::PrimeBucketIndexer;
+    ///
+    /// assert_eq!(PrimeBucketIndexer::with_capacity(0).unwrap().capacity(), 53);
+    /// assert_eq!(PrimeBucketIndexer::with_capacity(1000).unwrap().capacity(), 1543);
+    /// assert_eq!(PrimeBucketIndexer::with_capacity(u64::MAX), None);
+    /// ```
+    pub fn with_capacity(min_capacity: u64) -> Option<Self> {
+        PRIMES
+            .iter()
+            .position(|prime| prime.get() >= min_capacity)
+            .map(|level| Self { level })
+    }
+
+    /// Returns the current number of buckets, which is always a prime.
+    #[inline]
+    pub const fn capacity(&self) -> u64 {
+        PRIMES[self.level].get()
+    }
+
+    /// Returns the precomputed divisor for the current capacity.
+    #[inline]
+    pub const fn divisor(&self) -> DivisorU64 {
+        PRIMES[self.level]
+    }
+
+    /// Returns the bucket for `hash`, i.e. `hash % self.capacity()`.
+    #[inline]
+    pub const fn index(&self, hash: u64) -> u64 {
+        PRIMES[self.level].rem_of(hash)
+    }
+
+    /// Moves to the next larger capacity in the table, returning `false` and
+    /// leaving the capacity unchanged if it is already the largest.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickdiv::PrimeBucketIndexer;
+    ///
+    /// let mut indexer = PrimeBucketIndexer::with_capacity(u64::MAX / 4).unwrap();
+    /// assert!(indexer.grow());
+    /// assert!(!indexer.grow());
+    /// assert_eq!(indexer.capacity(), 13_835_058_055_282_163_729);
+    /// ```
+    #[inline]
+    pub fn grow(&mut self) -> bool {
+        if self.level + 1 == PRIMES.len() {
+            return false;
+        }
+        self.level += 1;
+        true
+    }
+
+    /// Moves to the next smaller capacity in the table, returning `false` and
+    /// leaving the capacity unchanged if it is already the smallest.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickdiv::PrimeBucketIndexer;
+    ///
+    /// let mut indexer = PrimeBucketIndexer::with_capacity(90).unwrap();
+    /// assert!(indexer.shrink());
+    /// assert_eq!(indexer.capacity(), 53);
+    /// assert!(!indexer.shrink());
+    /// ```
+    #[inline]
+    pub fn shrink(&mut self) -> bool {
+        if self.level == 0 {
+            return false;
+        }
+        self.level -= 1;
+        true
+    }
+}
+
+impl Default for PrimeBucketIndexer {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+macro_rules! prime_buckets_tests {
+    ($ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_lossless)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            // Deterministic Miller-Rabin test, which is exact for all 64-bit
+            // values with these bases.
+            fn is_prime(n: u64) -> bool {
+                const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+                if BASES.contains(&n) {
+                    return true;
+                }
+                if n < 2 || BASES.iter().any(|&p| n % p == 0) {
+                    return false;
+                }
+
+                let m = ModulusU64::new(n);
+                let s = (n - 1).trailing_zeros();
+                let d = (n - 1) >> s;
+                BASES.iter().all(|&a| {
+                    let mut x = m.pow_mod(a, d);
+                    if x == 1 || x == n - 1 {
+                        return true;
+                    }
+                    for _ in 1..s {
+                        x = m.mul_mod(x, x);
+                        if x == n - 1 {
+                            return true;
+                        }
+                    }
+                    false
+                })
+            }
+
+            #[test]
+            fn capacities_are_increasing_primes() {
+                let mut indexer = PrimeBucketIndexer::new();
+                let mut previous = 0;
+                loop {
+                    let capacity = indexer.capacity();
+                    assert!(is_prime(capacity), "{} is not prime", capacity);
+                    let (wide_capacity, wide_previous) = (capacity as u128 * 4, previous as u128);
+                    assert!(
+                        previous == 0
+                            || (7 * wide_previous..9 * wide_previous).contains(&wide_capacity)
+                    );
+                    assert_eq!(indexer.divisor().get(), capacity);
+                    previous = capacity;
+                    if !indexer.grow() {
+                        break;
+                    }
+                }
+                assert!(previous > u64::MAX / 2);
+            }
+
+            #[test]
+            fn shrink_undoes_grow() {
+                let mut indexer = PrimeBucketIndexer::default();
+                assert!(!indexer.shrink());
+                while indexer.grow() {}
+                let mut levels = 1;
+                while indexer.shrink() {
+                    levels += 1;
+                }
+                assert_eq!(indexer, PrimeBucketIndexer::new());
+                assert_eq!(levels, 59);
+            }
+
+            quickcheck! {
+                fn index_agrees_with_remainder(min_capacity: u64, hash: u64) -> bool {
+                    match PrimeBucketIndexer::with_capacity(min_capacity) {
+                        Some(indexer) => {
+                            let mut smaller = indexer;
+                            indexer.capacity() >= min_capacity
+                                && (!smaller.shrink() || smaller.capacity() < min_capacity)
+                                && indexer.index(hash) == hash % indexer.capacity()
+                        }
+                        None => min_capacity > 13_835_058_055_282_163_729,
+                    }
+                }
+            }
+        }
+    };
+}
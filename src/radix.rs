@@ -0,0 +1,302 @@
+// Digits used by write_radix, for all radixes up to 62.
+pub(crate) const RADIX_DIGITS: &[u8; 62] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+macro_rules! declare_digits_struct {
+    ($SelfT:ident, $DivisorT:ident, $BaseT:ty) => {
+        /// Iterator over the digits of a number in the radix given by a
+        #[doc = concat!("[`", stringify!($DivisorT), "`],")]
+        /// least significant digit first.
+        ///
+        /// Use `rev` to iterate over the digits starting from the most
+        /// significant one instead. Iterating from the back divides by a power
+        /// of the radix, so it is slower than iterating from the front. This
+        /// struct is created by
+        #[doc = concat!("[`", stringify!($DivisorT), "::digits_of`].")]
+        #[derive(Clone, Debug)]
+        pub struct $SelfT {
+            // The digits that have not been returned yet, from either end.
+            n: $BaseT,
+            // The radix to the power of `len - 1`, the place value of the most
+            // significant remaining digit.
+            power: $BaseT,
+            len: usize,
+            radix: $DivisorT,
+        }
+
+        impl Iterator for $SelfT {
+            type Item = $BaseT;
+
+            #[inline]
+            fn next(&mut self) -> Option<$BaseT> {
+                if self.len == 0 {
+                    return None;
+                }
+                let q = self.radix.div_of(self.n);
+                let digit = self.n - q * self.radix.get();
+                self.n = q;
+                self.power = self.radix.div_of(self.power);
+                self.len -= 1;
+                Some(digit)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.len, Some(self.len))
+            }
+        }
+
+        impl DoubleEndedIterator for $SelfT {
+            #[inline]
+            fn next_back(&mut self) -> Option<$BaseT> {
+                if self.len == 0 {
+                    return None;
+                }
+                let digit = self.n / self.power;
+                self.n -= digit * self.power;
+                self.power = self.radix.div_of(self.power);
+                self.len -= 1;
+                Some(digit)
+            }
+        }
+
+        impl ExactSizeIterator for $SelfT {}
+
+        impl core::iter::FusedIterator for $SelfT {}
+    };
+}
+
+macro_rules! radix_impl {
+    ($SelfT:ident, $DigitsT:ident, $BaseT:ty) => {
+        /// Returns an iterator over the digits of `n` in radix `self`, least
+        /// significant digit first.
+        ///
+        /// Zero has a single digit. Use `rev` to get the most significant digit
+        /// first instead.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `self` equals one.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(10);")]
+        /// assert!(d.digits_of(123).eq([3, 2, 1]));
+        /// assert!(d.digits_of(123).rev().eq([1, 2, 3]));
+        /// assert!(d.digits_of(0).eq([0]));
+        /// ```
+        pub fn digits_of(&self, n: $BaseT) -> $DigitsT {
+            let d = self.get();
+            assert!(d > 1, "radix must be at least 2");
+
+            let mut len = 1;
+            let mut power = 1;
+            let mut q = self.div_of(n);
+            while q > 0 {
+                len += 1;
+                power *= d;
+                q = self.div_of(q);
+            }
+
+            $DigitsT {
+                n,
+                power,
+                len,
+                radix: *self,
+            }
+        }
+
+        /// Returns the number of digits of `n` in radix `self`.
+        ///
+        /// Zero has a single digit.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `self` equals one.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(10);")]
+        /// assert_eq!(d.digit_count(0), 1);
+        /// assert_eq!(d.digit_count(99), 2);
+        /// assert_eq!(d.digit_count(100), 3);
+        /// ```
+        pub fn digit_count(&self, n: $BaseT) -> u32 {
+            assert!(self.get() > 1, "radix must be at least 2");
+
+            let mut count = 1;
+            let mut n = self.div_of(n);
+            while n > 0 {
+                count += 1;
+                n = self.div_of(n);
+            }
+            count
+        }
+
+        /// Returns the logarithm of `n` with base `self`, rounded down.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `n` equals zero, or if `self` equals one.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(3);")]
+        /// assert_eq!(d.ilog_of(1), 0);
+        /// assert_eq!(d.ilog_of(80), 3);
+        /// assert_eq!(d.ilog_of(81), 4);
+        /// ```
+        pub fn ilog_of(&self, n: $BaseT) -> u32 {
+            assert!(n > 0, "argument of integer logarithm must be positive");
+
+            self.digit_count(n) - 1
+        }
+
+        /// Writes `n` in radix `self` into `w`.
+        ///
+        /// Digits beyond `9` are written as the lowercase letters `a` to `z`,
+        /// followed by the uppercase letters `A` to `Z`, so radixes up to 36
+        /// agree with the formatting of the standard library.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if writing into `w` fails.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `self` is less than 2 or greater than 62.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(36);")]
+        /// let mut s = String::new();
+        /// d.write_radix(125, &mut s).unwrap();
+        /// assert_eq!(s, "3h");
+        /// ```
+        #[allow(clippy::cast_possible_truncation)]
+        pub fn write_radix(&self, n: $BaseT, w: &mut impl core::fmt::Write) -> core::fmt::Result {
+            assert!(self.get() <= 62, "radix must be at most 62");
+
+            // Collect the digits least significant first, from the end of the
+            // buffer, to avoid iterating over them from the back.
+            let mut buf = [0; <$BaseT>::BITS as usize];
+            let mut start = buf.len();
+            for digit in self.digits_of(n) {
+                start -= 1;
+                buf[start] = crate::radix::RADIX_DIGITS[digit as usize];
+            }
+            for &c in &buf[start..] {
+                w.write_char(c as char)?;
+            }
+            Ok(())
+        }
+    };
+}
+
+macro_rules! radix_tests {
+    ($SelfT:ident, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        mod $ModName {
+            use quickcheck::*;
+            use std::format;
+            use std::string::String;
+            use std::vec::Vec;
+
+            use super::*;
+
+            #[derive(Clone, Copy, Debug)]
+            struct Radix($BaseT);
+
+            impl Arbitrary for Radix {
+                fn arbitrary(g: &mut Gen) -> Self {
+                    Radix(<$BaseT>::arbitrary(g).max(2))
+                }
+            }
+
+            fn reference_digits(mut n: $BaseT, radix: $BaseT) -> Vec<$BaseT> {
+                let mut digits = std::vec![n % radix];
+                n /= radix;
+                while n > 0 {
+                    digits.push(n % radix);
+                    n /= radix;
+                }
+                digits
+            }
+
+            #[test]
+            #[should_panic(expected = "radix must be at least 2")]
+            fn rejects_unary_radix() {
+                let _ = $SelfT::new(1).digits_of(5);
+            }
+
+            #[test]
+            #[should_panic(expected = "argument of integer logarithm must be positive")]
+            fn ilog_rejects_zero() {
+                let _ = $SelfT::new(10).ilog_of(0);
+            }
+
+            #[test]
+            fn handles_extreme_values() {
+                let binary = $SelfT::new(2);
+                assert_eq!(binary.digit_count(<$BaseT>::MAX), <$BaseT>::BITS);
+                assert!(binary.digits_of(<$BaseT>::MAX).all(|digit| digit == 1));
+
+                let max = $SelfT::new(<$BaseT>::MAX);
+                assert!(max.digits_of(<$BaseT>::MAX).eq([0, 1]));
+                assert_eq!(max.ilog_of(<$BaseT>::MAX - 1), 0);
+            }
+
+            quickcheck! {
+                fn digits_agree_with_repeated_division(n: $BaseT, radix: Radix) -> bool {
+                    let d = $SelfT::new(radix.0);
+                    let expected = reference_digits(n, radix.0);
+
+                    d.digits_of(n).collect::<Vec<_>>() == expected
+                        && d.digits_of(n).rev().eq(expected.iter().rev().copied())
+                        && d.digits_of(n).len() == expected.len()
+                        && d.digit_count(n) as usize == expected.len()
+                        && (n == 0 || d.ilog_of(n) as usize == expected.len() - 1)
+                }
+            }
+
+            quickcheck! {
+                fn digits_meet_in_the_middle(n: $BaseT, radix: Radix, from_back: Vec<bool>) -> bool {
+                    let mut digits = $SelfT::new(radix.0).digits_of(n);
+                    let mut expected = reference_digits(n, radix.0);
+                    for &back in from_back.iter().chain(core::iter::repeat(&false)) {
+                        let (digit, reference) = if back {
+                            (digits.next_back(), expected.pop())
+                        } else {
+                            (digits.next(), (!expected.is_empty()).then(|| expected.remove(0)))
+                        };
+                        if digit != reference || digits.len() != expected.len() {
+                            return false;
+                        }
+                        if digit.is_none() {
+                            return true;
+                        }
+                    }
+                    unreachable!()
+                }
+            }
+
+            quickcheck! {
+                fn write_radix_agrees_with_std(n: $BaseT) -> bool {
+                    let mut s = [String::new(), String::new(), String::new(), String::new()];
+                    for (&radix, s) in [2, 8, 10, 16].iter().zip(s.iter_mut()) {
+                        $SelfT::new(radix).write_radix(n, s).unwrap();
+                    }
+                    s == [format!("{:b}", n), format!("{:o}", n), format!("{}", n), format!("{:x}", n)]
+                }
+            }
+        }
+    };
+}
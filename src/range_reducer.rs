@@ -0,0 +1,154 @@
+macro_rules! declare_range_reducer_struct {
+    ($SelfT:ident, $DivisorT:ident, $BaseT:ty, $SIZE:literal) => {
+        /// Maps
+        #[doc = concat!($SIZE)]
+        /// hashes onto the range `0..n` by multiplying and keeping the top
+        /// half of the product, also known as Lemire's fastrange.
+        ///
+        /// This takes a single multiplication, which is cheaper than the exact
+        /// remainder computed by
+        #[doc = concat!("[`", stringify!($DivisorT), "::rem_of`],")]
+        /// but it is not a remainder: `reduce(hash)` equals
+        /// `floor(hash * n / 2^N)`, so it depends mostly on the high bits of
+        /// the hash rather than on the low ones. Consecutive hashes land in the
+        /// same bucket, and `reduce(hash)` differs from `hash % n` in general.
+        ///
+        /// It is still fair: across all possible hashes, every value in `0..n`
+        /// is hit either `floor(2^N / n)` or `ceil(2^N / n)` times, the same as
+        /// with `%`. Use it when the hashes are well mixed and only the
+        /// distribution of the results matters, and use a divisor when the
+        /// exact remainder is needed.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let r = ", stringify!($SelfT), "::new(10);")]
+        /// assert_eq!(r.reduce(0), 0);
+        /// assert_eq!(r.reduce(12345), 0);
+        #[doc = concat!("assert_eq!(r.reduce(", stringify!($BaseT), "::MAX / 2), 4);")]
+        #[doc = concat!("assert_eq!(r.reduce(", stringify!($BaseT), "::MAX), 9);")]
+        /// ```
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub struct $SelfT {
+            n: $BaseT,
+        }
+
+        impl $SelfT {
+            /// Creates a reducer onto the range `0..n`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `n` equals zero.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let r = ", stringify!($SelfT), "::new(1000);")]
+            /// assert_eq!(r.get(), 1000);
+            /// ```
+            #[inline]
+            pub const fn new(n: $BaseT) -> Self {
+                let _ = 1 / n;
+                Self { n }
+            }
+
+            /// Returns the size of the output range.
+            #[inline]
+            pub const fn get(&self) -> $BaseT {
+                self.n
+            }
+
+            /// Maps `hash` onto `0..n`, computing `floor(hash * n / 2^N)`.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+            ///
+            #[doc = concat!("let r = ", stringify!($SelfT), "::new(3);")]
+            #[doc = concat!("assert_eq!(r.reduce(", stringify!($BaseT), "::MAX / 3), 0);")]
+            #[doc = concat!("assert_eq!(r.reduce(", stringify!($BaseT), "::MAX / 3 + 1), 1);")]
+            /// ```
+            #[inline]
+            pub const fn reduce(&self, hash: $BaseT) -> $BaseT {
+                <$DivisorT>::mulh(hash, self.n)
+            }
+        }
+    };
+}
+
+macro_rules! range_reducer_tests {
+    ($SelfT:ident, $BaseT:ident, $WiderT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_lossless, clippy::cast_possible_truncation)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            // Smallest hash mapped to bucket k, i.e. ceil(k * 2^N / n).
+            fn bucket_start(k: $BaseT, n: $BaseT) -> $WiderT {
+                let scaled = (k as $WiderT) << <$BaseT>::BITS;
+                (scaled + n as $WiderT - 1) / n as $WiderT
+            }
+
+            #[test]
+            #[should_panic(expected = "attempt to divide by zero")]
+            fn rejects_empty_range() {
+                let _ = $SelfT::new(0);
+            }
+
+            #[test]
+            fn spreads_uniform_hashes_evenly() {
+                // Multiples of an odd constant close to 2^N divided by the
+                // golden ratio are equidistributed, so each of the buckets
+                // should get its share up to a small discrepancy.
+                let step = (0x9e37_79b9_7f4a_7c15_u64 >> (64 - <$BaseT>::BITS)) as $BaseT | 1;
+                for &n in &[2, 7, 10, 97] {
+                    let r = $SelfT::new(n);
+                    let mut counts = [0u32; 97];
+                    let samples = 100 * 97;
+                    for i in 0..samples {
+                        counts[r.reduce(step.wrapping_mul(i as $BaseT)) as usize] += 1;
+                    }
+
+                    let expected = samples / n as u32;
+                    for &count in &counts[..n as usize] {
+                        assert!(
+                            count.max(expected) - count.min(expected) <= expected / 20,
+                            "{} buckets: {:?}",
+                            n,
+                            &counts[..n as usize]
+                        );
+                    }
+                    assert!(counts[n as usize..].iter().all(|&count| count == 0));
+                }
+            }
+
+            quickcheck! {
+                fn agrees_with_wide_product(n: $BaseT, hash: $BaseT) -> bool {
+                    let n = n.max(1);
+                    let expected = ((hash as $WiderT * n as $WiderT) >> <$BaseT>::BITS) as $BaseT;
+                    $SelfT::new(n).reduce(hash) == expected
+                }
+            }
+
+            quickcheck! {
+                fn buckets_get_equal_shares(n: $BaseT, k: $BaseT) -> bool {
+                    let n = n.max(1);
+                    let k = k % n;
+                    let r = $SelfT::new(n);
+
+                    let start = bucket_start(k, n);
+                    let end = bucket_start(k + 1, n);
+                    let share = ((1 as $WiderT) << <$BaseT>::BITS) / n as $WiderT;
+
+                    r.reduce(start as $BaseT) == k
+                        && (k == 0 || r.reduce((start - 1) as $BaseT) == k - 1)
+                        && (end - start == share || end - start == share + 1)
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,296 @@
+use crate::ModulusU64;
+
+/// Rolling polynomial hash over a sliding window of `u64` values, as used by
+/// the Rabin–Karp algorithm.
+///
+/// The hash of the values `x_0, ..., x_{n-1}` is
+/// `x_0 * base^(n-1) + ... + x_{n-2} * base + x_{n-1}` modulo the modulus.
+/// Values can be appended with [`push`](Self::push), and removed from the front
+/// of a full window with [`pop_front`](Self::pop_front), which uses a
+/// precomputed power of the base. Every reduction uses the precomputed
+/// reciprocal of the modulus.
+///
+/// # Examples
+/// ```
+/// use quickdiv::{ModulusU64, RollingHash};
+///
+/// let text = b"abracadabra";
+/// let mut hash = RollingHash::new(ModulusU64::new(1_000_000_007), 256, 4);
+/// for &byte in &text[..4] {
+///     hash.push(byte as u64);
+/// }
+///
+/// // Slide the window until it covers the second "abra".
+/// for i in 4..text.len() {
+///     hash.roll(text[i - 4] as u64, text[i] as u64);
+/// }
+/// assert_eq!(hash.hash(), hash.hash_bytes(b"abra"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct RollingHash {
+    modulus: ModulusU64,
+    base: u64,
+    window: usize,
+    // base^(window - 1) mod the modulus, the weight of the oldest value in a
+    // full window.
+    lead: u64,
+    hash: u64,
+    len: usize,
+}
+
+impl RollingHash {
+    /// Creates an empty rolling hash modulo `modulus` with the given `base`,
+    /// for windows holding `window` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` equals zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickdiv::{ModulusU64, RollingHash};
+    ///
+    /// let hash = RollingHash::new(ModulusU64::new(1_000_000_007), 31, 8);
+    /// assert_eq!(hash.base(), 31);
+    /// assert_eq!(hash.hash(), 0);
+    /// ```
+    pub fn new(modulus: ModulusU64, base: u64, window: usize) -> Self {
+        assert!(window > 0, "window must hold at least one value");
+
+        let base = modulus.reduce(base);
+        Self {
+            modulus,
+            base,
+            window,
+            lead: modulus.pow_mod(base, window as u64 - 1),
+            hash: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the modulus of the hash.
+    #[inline]
+    pub const fn modulus(&self) -> ModulusU64 {
+        self.modulus
+    }
+
+    /// Returns the base of the hash, reduced modulo the modulus.
+    #[inline]
+    pub const fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// Returns the number of values in a full window.
+    #[inline]
+    pub const fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the number of values currently hashed.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no values are currently hashed.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the hash of the values currently in the window.
+    #[inline]
+    pub const fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Appends `value` to the back of a window that is not yet full.
+    ///
+    /// Once the window is full, values must be removed with
+    /// [`pop_front`](Self::pop_front) or [`roll`](Self::roll) first, since
+    /// the weight of the oldest value is only known for full windows.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if the window already holds
+    /// [`window`](Self::window) values.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickdiv::{ModulusU64, RollingHash};
+    ///
+    /// let mut hash = RollingHash::new(ModulusU64::new(97), 10, 3);
+    /// hash.push(1);
+    /// hash.push(2);
+    /// hash.push(3);
+    /// assert_eq!(hash.hash(), 123 % 97);
+    /// ```
+    #[inline]
+    pub fn push(&mut self, value: u64) {
+        debug_assert!(self.len < self.window, "window is already full");
+
+        let shifted = self.modulus.mul_mod(self.hash, self.base);
+        self.hash = self.modulus.add_mod(shifted, value);
+        self.len += 1;
+    }
+
+    /// Removes `value` from the front of a full window.
+    ///
+    /// The result is only meaningful if `value` is the oldest value in the
+    /// window.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if the window does not hold exactly
+    /// [`window`](Self::window) values.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickdiv::{ModulusU64, RollingHash};
+    ///
+    /// let mut hash = RollingHash::new(ModulusU64::new(97), 10, 3);
+    /// hash.push(1);
+    /// hash.push(2);
+    /// hash.push(3);
+    /// hash.pop_front(1);
+    /// assert_eq!(hash.hash(), 23);
+    /// ```
+    #[inline]
+    pub fn pop_front(&mut self, value: u64) {
+        debug_assert!(self.len == self.window, "window is not full");
+
+        let weighted = self.modulus.mul_mod(value, self.lead);
+        self.hash = self.modulus.sub_mod(self.hash, weighted);
+        self.len -= 1;
+    }
+
+    /// Slides a full window by one value, removing `outgoing` from the front
+    /// and appending `incoming` to the back.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if the window does not hold exactly
+    /// [`window`](Self::window) values.
+    #[inline]
+    pub fn roll(&mut self, outgoing: u64, incoming: u64) {
+        self.pop_front(outgoing);
+        self.push(incoming);
+    }
+
+    /// Removes all values from the window.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.hash = 0;
+        self.len = 0;
+    }
+
+    /// Returns the hash of `values`, with the same modulus and base, without
+    /// changing the window.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickdiv::{ModulusU64, RollingHash};
+    ///
+    /// let hash = RollingHash::new(ModulusU64::new(97), 10, 3);
+    /// assert_eq!(hash.hash_slice(&[1, 2, 3, 4]), 1234 % 97);
+    /// ```
+    pub fn hash_slice(&self, values: &[u64]) -> u64 {
+        self.modulus
+            .divisor()
+            .rem_of_digits(values.iter().copied(), self.base)
+    }
+
+    /// Returns the hash of `bytes`, with the same modulus and base, without
+    /// changing the window.
+    ///
+    /// # Examples
+    /// ```
+    /// use quickdiv::{ModulusU64, RollingHash};
+    ///
+    /// let hash = RollingHash::new(ModulusU64::new(1_000_000_007), 256, 3);
+    /// assert_eq!(hash.hash_bytes(b"abc"), 0x616263);
+    /// ```
+    pub fn hash_bytes(&self, bytes: &[u8]) -> u64 {
+        self.modulus
+            .divisor()
+            .rem_of_digits(bytes.iter().map(|&byte| u64::from(byte)), self.base)
+    }
+}
+
+macro_rules! rolling_hash_tests {
+    ($ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_lossless)]
+        mod $ModName {
+            use quickcheck::*;
+            use std::vec::Vec;
+
+            use super::*;
+
+            #[test]
+            #[should_panic(expected = "window must hold at least one value")]
+            fn rejects_empty_window() {
+                let _ = RollingHash::new(ModulusU64::new(97), 10, 0);
+            }
+
+            #[test]
+            #[cfg(debug_assertions)]
+            #[should_panic(expected = "window is already full")]
+            fn rejects_push_to_full_window() {
+                let mut hash = RollingHash::new(ModulusU64::new(97), 10, 1);
+                hash.push(1);
+                hash.push(2);
+            }
+
+            #[test]
+            fn handles_max_modulus() {
+                let m = ModulusU64::new(u64::MAX);
+                let mut hash = RollingHash::new(m, u64::MAX - 1, 2);
+                hash.push(u64::MAX - 1);
+                hash.push(u64::MAX - 1);
+                hash.roll(u64::MAX - 1, 5);
+                assert_eq!(hash.hash(), hash.hash_slice(&[u64::MAX - 1, 5]));
+                assert_eq!(hash.len(), 2);
+
+                hash.clear();
+                assert!(hash.is_empty());
+                assert_eq!(hash.hash(), 0);
+            }
+
+            quickcheck! {
+                fn push_agrees_with_reference(m: u64, base: u64, values: Vec<u64>) -> bool {
+                    let m = m.max(1);
+                    let mut hash = RollingHash::new(ModulusU64::new(m), base, values.len().max(1));
+                    let expected = values.iter().fold(0u128, |acc, &value| {
+                        (acc * (base % m) as u128 + value as u128) % m as u128
+                    });
+                    for &value in &values {
+                        hash.push(value);
+                    }
+                    hash.hash() as u128 == expected && hash.hash() == hash.hash_slice(&values)
+                }
+            }
+
+            quickcheck! {
+                fn rolling_agrees_with_hashing_windows(m: u64, base: u64, window: u8, bytes: Vec<u8>) -> bool {
+                    let window = window as usize % 8 + 1;
+                    let mut hash = RollingHash::new(ModulusU64::new(m.max(1)), base, window);
+                    if bytes.len() < window {
+                        return true;
+                    }
+
+                    for &byte in &bytes[..window] {
+                        hash.push(byte as u64);
+                    }
+                    (window..=bytes.len()).all(|end| {
+                        let matches = hash.hash() == hash.hash_bytes(&bytes[end - window..end]);
+                        if end < bytes.len() {
+                            hash.roll(bytes[end - window] as u64, bytes[end] as u64);
+                        }
+                        matches
+                    })
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,152 @@
+macro_rules! sample_impl {
+    ($SelfT:ident, $BaseT:ty) => {
+        /// Returns a uniformly distributed value below `self`, given a source
+        /// of uniformly distributed random words.
+        ///
+        /// Taking the remainder of a random word alone would favor small values
+        /// whenever `self` does not divide `2^N`. Instead, words below the
+        /// threshold `2^N mod self` are rejected and `rng` is called again, so
+        /// the accepted words cover every remainder equally often. Fewer than
+        /// two words are needed on average, and the threshold and the
+        /// remainders use the precomputed divisor.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        /// // A tiny xorshift generator, standing in for any RNG.
+        /// let mut state: u32 = 2463534242;
+        /// let mut rng = || {
+        ///     state ^= state << 13;
+        ///     state ^= state >> 17;
+        ///     state ^= state << 5;
+        #[doc = concat!("    state as ", stringify!($BaseT))]
+        /// };
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(6);")]
+        /// let roll = d.sample_below(&mut rng);
+        /// assert!(roll < 6);
+        /// ```
+        pub fn sample_below<F: FnMut() -> $BaseT>(&self, mut rng: F) -> $BaseT {
+            // 2^N mod d, computed as (2^N - d) mod d.
+            let threshold = self.rem_of(self.get().wrapping_neg());
+            loop {
+                let word = rng();
+                if word >= threshold {
+                    return self.rem_of(word);
+                }
+            }
+        }
+
+        /// Returns a uniformly distributed value in `start..start + self`,
+        /// given a source of uniformly distributed random words.
+        ///
+        /// See [`sample_below`](Self::sample_below) for how bias is avoided.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the end of the range does not fit into
+        #[doc = concat!("`", stringify!($BaseT), "`.")]
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(6);")]
+        /// // A die roll, from 1 to 6, with a fixed word for illustration.
+        /// assert_eq!(d.sample_range(1, || 7), 2);
+        /// ```
+        pub fn sample_range<F: FnMut() -> $BaseT>(&self, start: $BaseT, rng: F) -> $BaseT {
+            assert!(
+                start.checked_add(self.get() - 1).is_some(),
+                "end of sampled range overflows"
+            );
+
+            start + self.sample_below(rng)
+        }
+    };
+}
+
+macro_rules! sample_tests {
+    ($DivisorT:ident, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_possible_truncation)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            #[test]
+            fn accepted_words_cover_remainders_equally() {
+                // Every word below the threshold is rejected, and the accepted
+                // words come in whole periods of n, so each remainder should
+                // be hit once per period.
+                for n in 1..=255 {
+                    let d = $DivisorT::new(n);
+                    let threshold = (<$BaseT>::MAX % n + 1) % n;
+                    let periods = ((<$BaseT>::MAX - threshold) / n).min(15) + 1;
+                    let mut counts = [0; 255];
+                    for word in 0..=threshold + (periods - 1) * n + (n - 1) {
+                        let mut calls = 0;
+                        let sample = d.sample_below(|| {
+                            calls += 1;
+                            if calls == 1 {
+                                word
+                            } else {
+                                <$BaseT>::MAX
+                            }
+                        });
+                        if calls == 1 {
+                            counts[sample as usize] += 1;
+                        }
+                    }
+                    let counts = &counts[..n as usize];
+                    assert!(
+                        counts.iter().all(|&count| count == periods),
+                        "{}: {:?}",
+                        n,
+                        counts
+                    );
+                }
+            }
+
+            #[test]
+            #[should_panic(expected = "end of sampled range overflows")]
+            fn rejects_overflowing_range() {
+                let _ = $DivisorT::new(2).sample_range(<$BaseT>::MAX, || 0);
+            }
+
+            quickcheck! {
+                fn rejects_words_below_threshold(d: $DivisorT, word: $BaseT) -> bool {
+                    let n = d.get();
+                    let threshold = (<$BaseT>::MAX % n + 1) % n;
+                    let mut calls = 0;
+                    let sample = d.sample_below(|| {
+                        calls += 1;
+                        if calls == 1 { word } else { <$BaseT>::MAX }
+                    });
+
+                    if word >= threshold {
+                        sample == word % n && calls == 1
+                    } else {
+                        sample == <$BaseT>::MAX % n && calls == 2
+                    }
+                }
+            }
+
+            quickcheck! {
+                fn samples_within_range(d: $DivisorT, start: $BaseT, word: $BaseT) -> TestResult {
+                    let n = d.get();
+                    if start.checked_add(n - 1).is_none() {
+                        return TestResult::discard();
+                    }
+
+                    let words = [word, <$BaseT>::MAX];
+                    let mut words = words.iter().copied();
+                    let sample = d.sample_range(start, || words.next().unwrap());
+                    TestResult::from_bool(sample >= start && sample - start < n)
+                }
+            }
+        }
+    };
+}
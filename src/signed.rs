@@ -1,5 +1,5 @@
 macro_rules! declare_signed_structs {
-    ($SelfT:ident, $InnerT:ident, $BaseT:ty, $SIZE:literal) => {
+    ($SelfT:ident, $InnerT:ident, $BaseT:ty, $UnsignedBaseT:ty, $SIZE:literal) => {
         /// Faster divisor for division and modulo operations by
         #[doc = concat!($SIZE)]
         /// signed integer values.
@@ -7,6 +7,9 @@ macro_rules! declare_signed_structs {
         #[derive(Clone, Copy)]
         pub struct $SelfT {
             inner: $InnerT,
+            reciprocal: $UnsignedBaseT,
+            odd_shift: u8,
+            inverse: $BaseT,
         }
 
         #[derive(Clone, Copy)]
@@ -34,6 +37,7 @@ macro_rules! signed_impl {
         ///
         #[doc = concat!("let d = ", stringify!($SelfT), "::new(-21);")]
         /// ```
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
         pub const fn new(d: $BaseT) -> $SelfT {
             // Forces a panic when d = 0, since we cannot use panic! in const.
             let _ = 1 / d;
@@ -42,6 +46,11 @@ macro_rules! signed_impl {
 
             let shift = Self::ilog2(ud);
 
+            // The reciprocal of |d| normalized so that its top bit is set, i.e.
+            // floor((2^(2N) - 1) / (|d| << (N - 1 - shift))) - 2^N, as used by
+            // the double-width arithmetic.
+            let mut reciprocal = <$UnsignedBaseT>::MAX;
+
             let inner = if ud.is_power_of_two() {
                 if d > 0 {
                     <$InnerT>::Shift(d, shift)
@@ -49,7 +58,23 @@ macro_rules! signed_impl {
                     <$InnerT>::ShiftAndNegate(d, shift)
                 }
             } else {
-                let (mut magic, rem) = Self::div_rem_wide_by_base(1 << (shift - 1), ud);
+                let (mut magic, rem) = Self::div_rem_wide_by_base(1 << (shift - 1), 0, ud);
+
+                // Extend floor(2^(N + shift - 1) / |d|) by two more quotient bits.
+                reciprocal = magic;
+                let mut r = rem;
+                let mut i = 0;
+                while i < 2 {
+                    reciprocal = reciprocal.wrapping_mul(2);
+                    let (doubled_r, overflowed) = r.overflowing_mul(2);
+                    if doubled_r >= ud || overflowed {
+                        reciprocal += 1;
+                        r = doubled_r.wrapping_sub(ud);
+                    } else {
+                        r = doubled_r;
+                    }
+                    i += 1;
+                }
 
                 let e = ud - rem;
 
@@ -71,7 +96,17 @@ macro_rules! signed_impl {
                 }
             };
 
-            Self { inner }
+            // The number of trailing zeros of d and the inverse of its odd part
+            // modulo 2^N, as used by div_exact.
+            let odd_shift = d.trailing_zeros() as u8;
+            let inverse = Self::odd_inverse(d >> odd_shift);
+
+            Self {
+                inner,
+                reciprocal,
+                odd_shift,
+                inverse,
+            }
         }
 
         /// Returns the value that was used to construct this divisor as a primitive type.
@@ -183,6 +218,37 @@ macro_rules! signed_impl {
             }
         }
 
+        /// Returns the result of dividing `n` by `self`, provided that `n` is a
+        /// multiple of `self`.
+        ///
+        /// This takes a single multiplication by the inverse of the odd part of
+        /// the divisor and a shift, which is cheaper than [`div_of`](Self::div_of).
+        /// If `n` is not a multiple of `self`, the result is unspecified. Overflow
+        /// wraps around in the same way as in `div_of`.
+        ///
+        /// # Panics
+        ///
+        /// Panics in debug builds if `n` is not divisible by `self`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(-12);")]
+        /// assert_eq!(d.div_exact(108), -9);
+        /// ```
+        #[inline]
+        #[allow(clippy::cast_lossless)]
+        pub const fn div_exact(&self, n: $BaseT) -> $BaseT {
+            // Forces a panic in debug builds when n is not a multiple of d,
+            // since we cannot use panic! in const.
+            #[cfg(debug_assertions)]
+            let _ = 1 / (self.divides(n) as $BaseT);
+
+            (n >> self.odd_shift).wrapping_mul(self.inverse)
+        }
+
+        #[allow(clippy::cast_sign_loss)]
         const fn abs(n: $BaseT) -> $UnsignedBaseT {
             if n < 0 {
                 ((-1i8) as $UnsignedBaseT).wrapping_mul(n as $UnsignedBaseT)
@@ -192,8 +258,11 @@ macro_rules! signed_impl {
         }
 
         // We have to implement our own const ilog2 to get MSRV below 1.67.
+        #[allow(clippy::cast_possible_truncation)]
         const fn ilog2(n: $UnsignedBaseT) -> u8 {
             (<$UnsignedBaseT>::BITS - 1 - n.leading_zeros()) as u8
         }
+
+        odd_inverse_impl! { $BaseT }
     };
 }
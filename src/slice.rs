@@ -0,0 +1,85 @@
+macro_rules! slice_impl {
+    ($SelfT:ident, $BaseT:ty) => {
+        slice_impl! { $SelfT, $BaseT, concat!(stringify!($SelfT), "::new(3)") }
+    };
+    ($SelfT:ident, $BaseT:ty, $NewThree:expr) => {
+        /// Returns the number of elements of `ns` that are divisible by `self`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", $NewThree, ";")]
+        /// assert_eq!(d.count_divisible(&[1, 3, 5, 6, 9, 10]), 3);
+        /// ```
+        #[inline]
+        pub fn count_divisible(&self, ns: &[$BaseT]) -> usize {
+            ns.iter().filter(|&&n| self.divides(n)).count()
+        }
+
+        /// Reorders `ns` in place so that all elements divisible by `self` come
+        /// before all elements that are not, and returns the number of divisible
+        /// elements.
+        ///
+        /// The relative order of the elements within each group is not preserved.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", $NewThree, ";")]
+        /// let mut ns = [1, 3, 5, 6, 9, 10];
+        /// let k = d.partition_divisible(&mut ns);
+        /// assert_eq!(k, 3);
+        /// assert!(ns[..k].iter().all(|&n| d.divides(n)));
+        /// assert!(ns[k..].iter().all(|&n| !d.divides(n)));
+        /// ```
+        pub fn partition_divisible(&self, ns: &mut [$BaseT]) -> usize {
+            let mut lo = 0;
+            let mut hi = ns.len();
+
+            loop {
+                while lo < hi && self.divides(ns[lo]) {
+                    lo += 1;
+                }
+                while lo < hi && !self.divides(ns[hi - 1]) {
+                    hi -= 1;
+                }
+                if lo == hi {
+                    return lo;
+                }
+
+                // Here ns[lo] is not divisible and ns[hi - 1] is, so lo < hi - 1.
+                ns.swap(lo, hi - 1);
+                lo += 1;
+                hi -= 1;
+            }
+        }
+
+        /// Moves all elements of `ns` divisible by `self` to the front of the
+        /// slice, preserving their relative order, and returns their number.
+        ///
+        /// The remaining elements are moved to the back of the slice in an
+        /// unspecified order.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", $NewThree, ";")]
+        /// let mut ns = [1, 3, 5, 6, 9, 10];
+        /// let k = d.retain_divisible(&mut ns);
+        /// assert_eq!(&ns[..k], &[3, 6, 9]);
+        /// ```
+        pub fn retain_divisible(&self, ns: &mut [$BaseT]) -> usize {
+            let mut count = 0;
+            for i in 0..ns.len() {
+                if self.divides(ns[i]) {
+                    ns.swap(count, i);
+                    count += 1;
+                }
+            }
+            count
+        }
+    };
+}
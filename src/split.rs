@@ -0,0 +1,211 @@
+macro_rules! declare_even_chunks_struct {
+    ($SelfT:ident, $DivisorT:ident, $BaseT:ty) => {
+        /// Iterator over the ranges of a balanced partition of `0..len` into as
+        /// many parts as the value of a
+        #[doc = concat!("[`", stringify!($DivisorT), "`].")]
+        ///
+        /// With `len = q * d + r`, the first `r` parts have `q + 1` elements and
+        /// the others `q`, so the `k`-th part starts at `k * q + min(k, r)`. This
+        /// struct is created by
+        #[doc = concat!("[`", stringify!($DivisorT), "::split_evenly`].")]
+        #[derive(Clone, Debug)]
+        pub struct $SelfT {
+            len: $BaseT,
+            quotient: $BaseT,
+            remainder: $BaseT,
+            // Divisors by the sizes of the longer and of the shorter parts,
+            // or by any non-zero value if there are no such parts.
+            long_size: $DivisorT,
+            short_size: $DivisorT,
+            front: $BaseT,
+            back: $BaseT,
+        }
+
+        impl $SelfT {
+            /// Returns the index of the part containing `index`, or `None` if
+            /// `index` is not less than the length being split.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use quickdiv::", stringify!($DivisorT), ";")]
+            ///
+            #[doc = concat!("let chunks = ", stringify!($DivisorT), "::new(3).split_evenly(10);")]
+            /// assert_eq!(chunks.chunk_of(3), Some(0));
+            /// assert_eq!(chunks.chunk_of(4), Some(1));
+            /// assert_eq!(chunks.chunk_of(9), Some(2));
+            /// assert_eq!(chunks.chunk_of(10), None);
+            /// ```
+            #[inline]
+            pub fn chunk_of(&self, index: $BaseT) -> Option<$BaseT> {
+                if index >= self.len {
+                    return None;
+                }
+
+                // The longer parts come first and end at `start(r)`, and every
+                // shorter part `k` starts at `k * q + r`.
+                let r = self.remainder;
+                if index < self.start(r) {
+                    Some(self.long_size.div_of(index))
+                } else {
+                    Some(self.short_size.div_of(index - r))
+                }
+            }
+
+            #[inline]
+            fn start(&self, k: $BaseT) -> $BaseT {
+                // At most `d * q + r = len`, so it always fits.
+                k * self.quotient + k.min(self.remainder)
+            }
+
+            #[inline]
+            fn chunk(&self, k: $BaseT) -> core::ops::Range<$BaseT> {
+                self.start(k)..self.start(k + 1)
+            }
+        }
+
+        impl Iterator for $SelfT {
+            type Item = core::ops::Range<$BaseT>;
+
+            #[inline]
+            fn next(&mut self) -> Option<core::ops::Range<$BaseT>> {
+                if self.front == self.back {
+                    return None;
+                }
+                self.front += 1;
+                Some(self.chunk(self.front - 1))
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                use core::convert::TryFrom;
+
+                match usize::try_from(self.back - self.front) {
+                    Ok(remaining) => (remaining, Some(remaining)),
+                    Err(_) => (usize::MAX, None),
+                }
+            }
+        }
+
+        impl DoubleEndedIterator for $SelfT {
+            #[inline]
+            fn next_back(&mut self) -> Option<core::ops::Range<$BaseT>> {
+                if self.front == self.back {
+                    return None;
+                }
+                self.back -= 1;
+                Some(self.chunk(self.back))
+            }
+        }
+
+        impl core::iter::FusedIterator for $SelfT {}
+    };
+}
+
+macro_rules! split_impl {
+    ($SelfT:ident, $EvenChunksT:ident, $BaseT:ty) => {
+        /// Splits `0..len` into `self` consecutive ranges, whose lengths differ
+        /// by at most one.
+        ///
+        /// The quotient and remainder of `len` by `self` are computed once, and
+        /// the `r` longer ranges come first. If `len` is less than `self`, the
+        /// trailing ranges are empty. Divisors by the sizes of the ranges are
+        /// also built once, for looking up the range of an index.
+        ///
+        /// The part containing a given index is found with
+        #[doc = concat!("[`", stringify!($EvenChunksT), "::chunk_of`]")]
+        /// on the returned iterator rather than on the divisor, since it depends
+        /// on the length being split.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(3);")]
+        /// assert!(d.split_evenly(10).eq([0..4, 4..7, 7..10]));
+        /// assert!(d.split_evenly(2).eq([0..1, 1..2, 2..2]));
+        /// ```
+        pub fn split_evenly(&self, len: $BaseT) -> $EvenChunksT {
+            let (quotient, remainder) = self.div_rem_of(len);
+            $EvenChunksT {
+                len,
+                quotient,
+                remainder,
+                // Only used if there are longer parts, in which case
+                // `quotient + 1 <= len` does not overflow.
+                long_size: Self::new(quotient.saturating_add(1)),
+                // Only used if the shorter parts are non-empty.
+                short_size: Self::new(quotient.max(1)),
+                front: 0,
+                back: self.get(),
+            }
+        }
+    };
+}
+
+macro_rules! split_tests {
+    ($DivisorT:ident, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_lossless, clippy::cast_possible_truncation)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            #[test]
+            fn splits_full_range() {
+                let d = $DivisorT::new(1);
+                assert!(d.split_evenly(<$BaseT>::MAX).eq([0..<$BaseT>::MAX]));
+                assert_eq!(d.split_evenly(<$BaseT>::MAX).chunk_of(<$BaseT>::MAX - 1), Some(0));
+
+                let d = $DivisorT::new(<$BaseT>::MAX);
+                let chunks = d.split_evenly(<$BaseT>::MAX);
+                assert_eq!(chunks.clone().next_back(), Some(<$BaseT>::MAX - 1..<$BaseT>::MAX));
+                assert_eq!(chunks.chunk_of(<$BaseT>::MAX - 1), Some(<$BaseT>::MAX - 1));
+            }
+
+            quickcheck! {
+                fn div_rem_of_agrees_with_operators(d: $BaseT, n: $BaseT) -> bool {
+                    let d = d.max(1);
+                    $DivisorT::new(d).div_rem_of(n) == (n / d, n % d)
+                }
+            }
+
+            quickcheck! {
+                fn splits_into_balanced_chunks(d: u8, len: $BaseT) -> bool {
+                    let d = d.max(1) as $BaseT;
+                    let chunks = $DivisorT::new(d).split_evenly(len);
+
+                    let mut expected_start = 0;
+                    let mut count = 0;
+                    for chunk in chunks.clone() {
+                        let size = chunk.end - chunk.start;
+                        if chunk.start != expected_start || (size != len / d && size != len / d + 1) {
+                            return false;
+                        }
+                        expected_start = chunk.end;
+                        count += 1;
+                    }
+
+                    count == d
+                        && expected_start == len
+                        && chunks.clone().rev().zip((0..d).rev()).all(|(chunk, k)| chunks.clone().nth(k as usize) == Some(chunk))
+                }
+            }
+
+            quickcheck! {
+                fn finds_chunk_of_index(d: u8, len: u16, index: u16) -> bool {
+                    let d = d.max(1) as $BaseT;
+                    let len = len as $BaseT;
+                    let index = index as $BaseT;
+                    let chunks = $DivisorT::new(d).split_evenly(len);
+
+                    let expected = chunks
+                        .clone()
+                        .position(|chunk| chunk.contains(&index))
+                        .map(|k| k as $BaseT);
+                    chunks.chunk_of(index) == expected
+                }
+            }
+        }
+    };
+}
@@ -1,8 +1,10 @@
 macro_rules! tests {
     ($SelfT:ty, $BaseT:ident) => {
         #[cfg(test)]
+        #[allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
         mod $BaseT {
             use quickcheck::*;
+            use std::vec::Vec;
 
             use super::*;
 
@@ -17,10 +19,22 @@ macro_rules! tests {
 
             #[test]
             #[should_panic]
+            #[allow(clippy::should_panic_without_expect)]
             fn cannot_create_zero_divisors() {
                 let _ = <$SelfT>::new(0);
             }
 
+            #[test]
+            fn agrees_with_builtin_near_zero() {
+                for d in (-64i16..=64).map(|d| d as $BaseT).filter(|&d| d != 0) {
+                    let divisor = <$SelfT>::new(d);
+                    for n in (-300i16..=300).map(|n| n as $BaseT) {
+                        assert_eq!(n / divisor, n.wrapping_div(d), "{} / {}", n, d);
+                        assert_eq!(n % divisor, n.wrapping_rem(d), "{} % {}", n, d);
+                    }
+                }
+            }
+
             quickcheck! {
                 fn is_multiple_of_five(x: $BaseT) -> bool {
                     let divisor = <$SelfT>::new(5);
@@ -65,6 +79,114 @@ macro_rules! tests {
                     d.get().wrapping_mul(x / d) + (x % d) == x
                 }
             }
+
+            quickcheck! {
+                fn count_divisible_agrees_with_filter(ns: Vec<$BaseT>, d: $SelfT) -> bool {
+                    let expected = ns.iter().filter(|&&n| n.wrapping_rem(d.get()) == 0).count();
+                    d.count_divisible(&ns) == expected
+                }
+            }
+
+            quickcheck! {
+                fn partition_divisible_splits_slice(ns: Vec<$BaseT>, d: $SelfT) -> bool {
+                    let mut partitioned = ns.clone();
+                    let k = d.partition_divisible(&mut partitioned);
+                    let (divisible, rest) = partitioned.split_at(k);
+
+                    let mut original = ns.clone();
+                    let mut permuted = partitioned.clone();
+                    original.sort_unstable();
+                    permuted.sort_unstable();
+
+                    original == permuted
+                        && divisible.iter().all(|&n| d.divides(n))
+                        && rest.iter().all(|&n| !d.divides(n))
+                }
+            }
+
+            quickcheck! {
+                fn retain_divisible_agrees_with_filter(ns: Vec<$BaseT>, d: $SelfT) -> bool {
+                    let expected: Vec<$BaseT> = ns.iter().copied().filter(|&n| d.divides(n)).collect();
+                    let mut retained = ns.clone();
+                    let k = d.retain_divisible(&mut retained);
+                    retained[..k] == expected[..]
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+macro_rules! reference_wide_impl {
+    ($BaseT:ty) => {
+        // Schoolbook binary multiplication, returning the top and bottom halves
+        // of the product. Not needed by every test module that uses this macro.
+        #[allow(dead_code)]
+        fn reference_mul_wide(x: $BaseT, y: $BaseT) -> ($BaseT, $BaseT) {
+            let (mut hi, mut lo): ($BaseT, $BaseT) = (0, 0);
+            for i in (0..<$BaseT>::BITS).rev() {
+                hi = (hi << 1) | (lo >> (<$BaseT>::BITS - 1));
+                lo <<= 1;
+                if (y >> i) & 1 == 1 {
+                    let (sum, carry) = lo.overflowing_add(x);
+                    lo = sum;
+                    hi += carry as $BaseT;
+                }
+            }
+            (hi, lo)
+        }
+
+        // Schoolbook binary long division of hi * 2^N + lo by d, for hi < d.
+        fn reference_div_rem_wide(hi: $BaseT, lo: $BaseT, d: $BaseT) -> ($BaseT, $BaseT) {
+            let mut q = 0;
+            let mut r = hi;
+            for i in (0..<$BaseT>::BITS).rev() {
+                let overflowed = r >> (<$BaseT>::BITS - 1) == 1;
+                r = (r << 1) | ((lo >> i) & 1);
+                q <<= 1;
+                if overflowed || r >= d {
+                    r = r.wrapping_sub(d);
+                    q |= 1;
+                }
+            }
+            (q, r)
+        }
+    };
+}
+
+macro_rules! signed_tests {
+    ($SelfT:ty, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        mod $ModName {
+            use super::*;
+
+            #[test]
+            fn mulh_of_negative_factors() {
+                assert_eq!(<$SelfT>::mulh(-1, -1), 0);
+                assert_eq!(<$SelfT>::mulh(-1, 1), -1);
+                assert_eq!(
+                    <$SelfT>::mulh($BaseT::MIN, $BaseT::MIN),
+                    1 << ($BaseT::BITS - 2)
+                );
+                assert_eq!(<$SelfT>::mulh($BaseT::MIN, $BaseT::MAX), $BaseT::MIN >> 1);
+                assert_eq!(<$SelfT>::mulh($BaseT::MAX, $BaseT::MAX), $BaseT::MAX >> 1);
+            }
+
+            // Negative dividends whose lower half has all bits set, for which
+            // the product of the lower halves in mulh does not fit into the
+            // signed type.
+            #[test]
+            fn agrees_with_builtin_on_negative_dividends() {
+                let lower_half: $BaseT = (1 << ($BaseT::BITS / 2)) - 1;
+                for d in (-64i8..=64).map(<$BaseT>::from).filter(|&d| d != 0) {
+                    let divisor = <$SelfT>::new(d);
+                    for k in 0..64 {
+                        let n = $BaseT::MIN | (k << ($BaseT::BITS / 2)) | lower_half;
+                        assert_eq!(divisor.div_of(n), n / d, "{} / {}", n, d);
+                        assert_eq!(divisor.rem_of(n), n % d, "{} % {}", n, d);
+                    }
+                }
+            }
         }
     };
 }
@@ -1,28 +1,28 @@
 macro_rules! impl_traits {
-    ($SelfT:ty, $BaseT:ty) => {
-        impl PartialEq for $SelfT {
+    ([$($Generics:tt)*], $SelfT:ty, $BaseT:ty) => {
+        impl<$($Generics)*> PartialEq for $SelfT {
             #[inline]
             fn eq(&self, other: &Self) -> bool {
                 self.get() == other.get()
             }
         }
 
-        impl Eq for $SelfT {}
+        impl<$($Generics)*> Eq for $SelfT {}
 
-        impl core::hash::Hash for $SelfT {
+        impl<$($Generics)*> core::hash::Hash for $SelfT {
             #[inline]
             fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
                 self.get().hash(state);
             }
         }
 
-        impl core::fmt::Debug for $SelfT {
+        impl<$($Generics)*> core::fmt::Debug for $SelfT {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 write!(f, "{}", self.get())
             }
         }
 
-        impl core::ops::Div<$SelfT> for $BaseT {
+        impl<$($Generics)*> core::ops::Div<$SelfT> for $BaseT {
             type Output = $BaseT;
 
             #[inline]
@@ -31,14 +31,14 @@ macro_rules! impl_traits {
             }
         }
 
-        impl core::ops::DivAssign<$SelfT> for $BaseT {
+        impl<$($Generics)*> core::ops::DivAssign<$SelfT> for $BaseT {
             #[inline]
             fn div_assign(&mut self, rhs: $SelfT) {
                 *self = rhs.div_of(*self)
             }
         }
 
-        impl core::ops::Rem<$SelfT> for $BaseT {
+        impl<$($Generics)*> core::ops::Rem<$SelfT> for $BaseT {
             type Output = $BaseT;
 
             #[inline]
@@ -47,11 +47,14 @@ macro_rules! impl_traits {
             }
         }
 
-        impl core::ops::RemAssign<$SelfT> for $BaseT {
+        impl<$($Generics)*> core::ops::RemAssign<$SelfT> for $BaseT {
             #[inline]
             fn rem_assign(&mut self, rhs: $SelfT) {
                 *self = rhs.rem_of(*self)
             }
         }
     };
+    ($SelfT:ty, $BaseT:ty) => {
+        impl_traits! { [], $SelfT, $BaseT }
+    };
 }
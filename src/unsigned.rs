@@ -1,11 +1,15 @@
 macro_rules! declare_unsigned_structs {
-    ($SelfT:ident, $InnerT:ident, $BaseT:ty, $SIZE:literal) => {
+    ($(#[$attr:meta])* $SelfT:ident, $InnerT:ident, $BaseT:ty, $SIZE:literal) => {
         /// Faster divisor for division and modulo operations by
         #[doc = concat!($SIZE)]
         /// unsigned integer values.
+        $(#[$attr])*
         #[derive(Clone, Copy)]
         pub struct $SelfT {
             inner: $InnerT,
+            reciprocal: $BaseT,
+            odd_shift: u8,
+            inverse: $BaseT,
         }
 
         #[derive(Clone, Copy)]
@@ -31,33 +35,50 @@ macro_rules! unsigned_impl {
         ///
         #[doc = concat!("let d = ", stringify!($SelfT), "::new(42);")]
         /// ```
+        #[allow(clippy::cast_possible_truncation)]
         pub const fn new(d: $BaseT) -> $SelfT {
             // Forces a panic when d = 0, since we cannot use panic! in const.
             let _ = 1 / d;
 
             let shift = Self::ilog2(d);
 
-            let inner = if d.is_power_of_two() {
-                <$InnerT>::Shift(d, shift)
+            // The reciprocal of d normalized so that its top bit is set, i.e.
+            // floor((2^(2N) - 1) / (d << (N - 1 - shift))) - 2^N, as used by
+            // div_rem_wide.
+            let (inner, reciprocal) = if d.is_power_of_two() {
+                (<$InnerT>::Shift(d, shift), <$BaseT>::MAX)
             } else {
-                let (mut magic, rem) = Self::div_rem_wide_by_base(1 << shift, d);
+                let (magic, rem) = Self::div_rem_wide_by_base(1 << shift, 0, d);
+
+                let mut reciprocal = magic.wrapping_mul(2);
+                let (doubled_rem, overflowed) = rem.overflowing_mul(2);
+                if doubled_rem >= d || overflowed {
+                    reciprocal += 1;
+                }
 
                 let e = d - rem;
 
                 if e < 1 << shift {
-                    <$InnerT>::MultiplyShift(d, magic + 1, shift)
+                    (<$InnerT>::MultiplyShift(d, magic + 1, shift), reciprocal)
                 } else {
-                    magic = magic.wrapping_mul(2);
-                    let (doubled_rem, overflowed) = rem.overflowing_mul(2);
-                    if doubled_rem >= d || overflowed {
-                        magic += 1;
-                    }
-
-                    <$InnerT>::MultiplyAddShift(d, magic + 1, shift)
+                    (
+                        <$InnerT>::MultiplyAddShift(d, reciprocal + 1, shift),
+                        reciprocal,
+                    )
                 }
             };
 
-            Self { inner }
+            // The number of trailing zeros of d and the inverse of its odd part
+            // modulo 2^N, as used by div_exact.
+            let odd_shift = d.trailing_zeros() as u8;
+            let inverse = Self::odd_inverse(d >> odd_shift);
+
+            Self {
+                inner,
+                reciprocal,
+                odd_shift,
+                inverse,
+            }
         }
 
         /// Returns the value that was used to construct this divisor as a primitive type.
@@ -131,9 +152,166 @@ macro_rules! unsigned_impl {
             }
         }
 
+        /// Returns both the quotient and the remainder of dividing `n` by
+        /// `self`, computing the quotient only once.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(11);")]
+        /// assert_eq!(d.div_rem_of(30), (2, 8));
+        /// ```
+        #[inline]
+        pub const fn div_rem_of(&self, n: $BaseT) -> ($BaseT, $BaseT) {
+            let q = self.div_of(n);
+            (q, n - self.get() * q)
+        }
+
+        /// Returns the result of dividing `n` by `self`, provided that `n` is a
+        /// multiple of `self`.
+        ///
+        /// This takes a single multiplication by the inverse of the odd part of
+        /// the divisor and a shift, which is cheaper than [`div_of`](Self::div_of).
+        /// If `n` is not a multiple of `self`, the result is unspecified.
+        ///
+        /// # Panics
+        ///
+        /// Panics in debug builds if `n` is not divisible by `self`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(12);")]
+        /// assert_eq!(d.div_exact(108), 9);
+        /// ```
+        #[inline]
+        #[allow(clippy::cast_lossless)]
+        pub const fn div_exact(&self, n: $BaseT) -> $BaseT {
+            // Forces a panic in debug builds when n is not a multiple of d,
+            // since we cannot use panic! in const.
+            #[cfg(debug_assertions)]
+            let _ = 1 / (self.divides(n) as $BaseT);
+
+            (n >> self.odd_shift).wrapping_mul(self.inverse)
+        }
+
+        /// Returns the quotient and remainder of dividing the double-width value
+        #[doc = concat!("`hi * 2^", stringify!($BaseT), "::BITS + lo` by `self`.")]
+        ///
+        /// This uses the precomputed reciprocal of the divisor to replace the
+        /// double-width division with a couple of multiplications, as described by
+        /// Möller and Granlund in *Improved division by invariant integers*.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `hi` is not less than the divisor, since the quotient would
+        /// then not fit into a single word. To divide such values, first divide
+        /// `hi` by `self`, and then divide the remainder and `lo`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::", stringify!($SelfT), ";")]
+        ///
+        #[doc = concat!("let d = ", stringify!($SelfT), "::new(10);")]
+        #[doc = concat!("let (q, r) = d.div_rem_wide(3, ", stringify!($BaseT), "::MAX);")]
+        #[doc = concat!("assert_eq!(q, ", stringify!($BaseT), "::MAX / 10 * 4 + 2);")]
+        /// assert_eq!(r, 3);
+        /// ```
+        #[inline]
+        pub fn div_rem_wide(&self, hi: $BaseT, lo: $BaseT) -> ($BaseT, $BaseT) {
+            let d = self.get();
+            assert!(hi < d, "quotient does not fit into a single word");
+
+            Self::div_rem_wide_preinv(hi, lo, d, self.reciprocal)
+        }
+
+        /// Divide a 2N-bit dividend by an N-bit divisor with remainder, given the
+        /// normalized reciprocal of the divisor and assuming that `hi < d`.
+        ///
+        /// Adapted from Algorithm 4 in Möller and Granlund, *Improved division by
+        /// invariant integers*.
+        #[inline]
+        const fn div_rem_wide_preinv(
+            hi: $BaseT,
+            lo: $BaseT,
+            d: $BaseT,
+            reciprocal: $BaseT,
+        ) -> ($BaseT, $BaseT) {
+            // Normalize the divisor so that its top bit is set, and shift the
+            // dividend by the same amount.
+            let s = d.leading_zeros();
+            let d = d << s;
+            let (u1, u0) = if s == 0 {
+                (hi, lo)
+            } else {
+                ((hi << s) | (lo >> (<$BaseT>::BITS - s)), lo << s)
+            };
+
+            let (q0, carry) = reciprocal.wrapping_mul(u1).overflowing_add(u0);
+            let mut q = Self::mulh(reciprocal, u1)
+                .wrapping_add(u1)
+                .wrapping_add(carry as $BaseT)
+                .wrapping_add(1);
+
+            let mut r = u0.wrapping_sub(q.wrapping_mul(d));
+            if r > q0 {
+                q = q.wrapping_sub(1);
+                r = r.wrapping_add(d);
+            }
+            if r >= d {
+                q += 1;
+                r -= d;
+            }
+
+            (q, r >> s)
+        }
+
         // We have to implement our own const ilog2 to get MSRV below 1.67.
+        #[allow(clippy::cast_possible_truncation)]
         const fn ilog2(n: $BaseT) -> u8 {
             (<$BaseT>::BITS - 1 - n.leading_zeros()) as u8
         }
+
+        odd_inverse_impl! { $BaseT }
+    };
+}
+
+macro_rules! div_rem_wide_tests {
+    ($SelfT:ty, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            reference_wide_impl! { $BaseT }
+
+            #[test]
+            fn div_rem_wide_edge_cases() {
+                for &d in &[1, 2, 3, $BaseT::MAX / 2, $BaseT::MAX / 2 + 1, $BaseT::MAX - 1, $BaseT::MAX] {
+                    let divisor = <$SelfT>::new(d);
+                    for &lo in &[0, 1, $BaseT::MAX] {
+                        for &hi in &[0, d / 2, d - 1] {
+                            assert_eq!(divisor.div_rem_wide(hi, lo), reference_div_rem_wide(hi, lo, d));
+                        }
+                    }
+                }
+            }
+
+            #[test]
+            #[should_panic(expected = "quotient does not fit into a single word")]
+            fn div_rem_wide_rejects_overflowing_quotients() {
+                let _ = <$SelfT>::new(7).div_rem_wide(7, 0);
+            }
+
+            quickcheck! {
+                fn div_rem_wide_agrees_with_long_division(hi: $BaseT, lo: $BaseT, d: $SelfT) -> bool {
+                    let hi = hi % d;
+                    d.div_rem_wide(hi, lo) == reference_div_rem_wide(hi, lo, d.get())
+                }
+            }
+        }
     };
 }
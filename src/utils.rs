@@ -5,6 +5,7 @@ macro_rules! widen_mulh_impl {
         /// Works by extending the factors to 2N-bits, using the built-in 2N-by-2N-bit
         /// multiplication and shifting right to the top half only.
         #[inline]
+        #[allow(clippy::cast_possible_truncation)]
         const fn mulh(x: $BaseT, y: $BaseT) -> $BaseT {
             (((x as $WiderT) * (y as $WiderT)) >> <$BaseT>::BITS) as $BaseT
         }
@@ -22,8 +23,11 @@ macro_rules! mulh_impl {
 
             let x_low = x & LOWER_HALF_MASK;
             let y_low = y & LOWER_HALF_MASK;
+            // The product of the lower halves is unsigned even when the
+            // factors are signed, so its top half must be extracted with a
+            // logical rather than an arithmetic shift.
             let t = x_low.wrapping_mul(y_low);
-            let k = t >> HALF_WIDTH_BITS;
+            let k = (t >> HALF_WIDTH_BITS) & LOWER_HALF_MASK;
 
             let x_high = x >> HALF_WIDTH_BITS;
             let t = x_high.wrapping_mul(y_low) + k;
@@ -42,13 +46,13 @@ macro_rules! mulh_impl {
 macro_rules! widen_div_rem_impl {
     ($BaseT:ty, $WiderT:ty) => {
         /// Divide a 2N-bit dividend by an N-bit divisor with remainder, assuming
-        /// that the result fits into N bits and that the lower half of bits of the
-        /// dividend are all zero.
+        /// that the result fits into N bits, i.e. that `hi < d`.
         ///
         /// Works by extending the dividend to 2N-bits and then using the built-in
         /// 2N-by-2N-bit division method.
-        const fn div_rem_wide_by_base(top_half: $BaseT, d: $BaseT) -> ($BaseT, $BaseT) {
-            let n = (top_half as $WiderT) << <$BaseT>::BITS;
+        #[allow(clippy::cast_possible_truncation)]
+        const fn div_rem_wide_by_base(hi: $BaseT, lo: $BaseT, d: $BaseT) -> ($BaseT, $BaseT) {
+            let n = ((hi as $WiderT) << <$BaseT>::BITS) | (lo as $WiderT);
             let quot = (n / (d as $WiderT)) as $BaseT;
             let rem = (n % (d as $WiderT)) as $BaseT;
             (quot, rem)
@@ -59,11 +63,10 @@ macro_rules! widen_div_rem_impl {
 macro_rules! divlu_impl {
     ($BaseT:ty) => {
         /// Divide a 2N-bit dividend by an N-bit divisor with remainder, assuming
-        /// that the result fits into N bits and that the lower half of bits of the
-        /// dividend are all zero.
+        /// that the result fits into N bits, i.e. that `hi < d`.
         ///
         /// Adapted from Figure 9-3 in Hacker's Delight, 2nd Ed.
-        const fn div_rem_wide_by_base(top_half: $BaseT, d: $BaseT) -> ($BaseT, $BaseT) {
+        const fn div_rem_wide_by_base(hi: $BaseT, lo: $BaseT, d: $BaseT) -> ($BaseT, $BaseT) {
             const HALF_WORD_BITS: u32 = <$BaseT>::BITS / 2;
 
             const BASE: $BaseT = 1 << HALF_WORD_BITS;
@@ -73,13 +76,21 @@ macro_rules! divlu_impl {
             let vn1 = v >> HALF_WORD_BITS;
             let vn0 = v & (BASE - 1);
 
-            let un32 = top_half << s;
+            let un32 = if s == 0 {
+                hi
+            } else {
+                (hi << s) | (lo >> (<$BaseT>::BITS - s))
+            };
+            let un10 = lo << s;
+
+            let un1 = un10 >> HALF_WORD_BITS;
+            let un0 = un10 & (BASE - 1);
 
             let mut q1 = un32 / vn1;
             let mut rhat = un32 - q1 * vn1;
 
             loop {
-                if q1 >= BASE || q1 * vn0 > (rhat << HALF_WORD_BITS) {
+                if q1 >= BASE || q1 * vn0 > (rhat << HALF_WORD_BITS) + un1 {
                     q1 -= 1;
                     rhat += vn1;
 
@@ -90,13 +101,13 @@ macro_rules! divlu_impl {
                 break;
             }
 
-            let un21 = (un32 << HALF_WORD_BITS).wrapping_sub(q1.wrapping_mul(v));
+            let un21 = ((un32 << HALF_WORD_BITS) + un1).wrapping_sub(q1.wrapping_mul(v));
 
             let mut q0 = un21 / vn1;
             rhat = un21 - q0 * vn1;
 
             loop {
-                if q0 >= BASE || q0 * vn0 > (rhat << HALF_WORD_BITS) {
+                if q0 >= BASE || q0 * vn0 > (rhat << HALF_WORD_BITS) + un0 {
                     q0 -= 1;
                     rhat += vn1;
 
@@ -107,9 +118,58 @@ macro_rules! divlu_impl {
                 break;
             }
 
-            let r = ((un21 << HALF_WORD_BITS).wrapping_sub(q0.wrapping_mul(v))) >> s;
+            let r = (((un21 << HALF_WORD_BITS) + un0).wrapping_sub(q0.wrapping_mul(v))) >> s;
 
             ((q1 << HALF_WORD_BITS) + q0, r)
         }
     };
 }
+
+macro_rules! odd_inverse_impl {
+    ($BaseT:ty) => {
+        /// Returns the multiplicative inverse of an odd word modulo 2^N.
+        ///
+        /// Every odd `d` is its own inverse modulo 8, and each step of Newton's
+        /// iteration doubles the number of correct low bits.
+        const fn odd_inverse(d: $BaseT) -> $BaseT {
+            let mut inverse = d;
+            let mut bits = 3;
+            while bits < <$BaseT>::BITS {
+                inverse = inverse.wrapping_mul((2 as $BaseT).wrapping_sub(d.wrapping_mul(inverse)));
+                bits *= 2;
+            }
+            inverse
+        }
+    };
+}
+
+macro_rules! div_exact_tests {
+    ($SelfT:ty, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            quickcheck! {
+                fn div_exact_agrees_with_div_of(x: $BaseT, d: $SelfT) -> bool {
+                    let n = x.wrapping_sub(x.wrapping_rem(d.get()));
+                    d.div_exact(n) == d.div_of(n)
+                }
+            }
+
+            #[test]
+            #[cfg(debug_assertions)]
+            #[should_panic(expected = "attempt to divide by zero")]
+            fn div_exact_rejects_non_multiples() {
+                let _ = <$SelfT>::new(6).div_exact(9);
+            }
+
+            #[test]
+            fn div_exact_in_const_context() {
+                const QUOTIENT: $BaseT = <$SelfT>::new(12).div_exact(108);
+                assert_eq!(QUOTIENT, 9);
+            }
+        }
+    };
+}
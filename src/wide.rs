@@ -0,0 +1,287 @@
+//! Double-width multiplication and division of primitive integers.
+//!
+//! These are the same helpers that the divisors use internally. They are
+//! implemented using the built-in arithmetic of the next wider type where one
+//! exists, and following Hacker's Delight otherwise.
+//!
+//! Double-width values are represented as a pair `(hi, lo)` of their top and
+//! bottom halves, standing for `hi * 2^N + lo`, where `N` is the width of the
+//! type. For signed types, only the top half carries the sign, so the bottom
+//! half is unsigned.
+//!
+//! # Examples
+//! ```
+//! use quickdiv::wide;
+//!
+//! let (hi, lo) = wide::mul_wide_u64(u64::MAX, 10);
+//! assert_eq!((hi, lo), (9, u64::MAX - 9));
+//! assert_eq!(wide::div_wide_u64(hi, lo, 10), Some((u64::MAX, 0)));
+//! ```
+
+use crate::{
+    DivisorI128, DivisorI16, DivisorI32, DivisorI64, DivisorI8, DivisorIsize, DivisorU128,
+    DivisorU16, DivisorU32, DivisorU64, DivisorU8, DivisorUsize,
+};
+
+macro_rules! wide_unsigned_fns {
+    ($mulh:ident, $mul_wide:ident, $div_wide:ident, $DivisorT:ident, $BaseT:ident) => {
+        /// Returns the top half of the double-width product of `x` and `y`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::wide::", stringify!($mulh), ";")]
+        ///
+        #[doc = concat!("assert_eq!(", stringify!($mulh), "(", stringify!($BaseT), "::MAX, 3), 2);")]
+        /// ```
+        #[inline]
+        pub const fn $mulh(x: $BaseT, y: $BaseT) -> $BaseT {
+            <$DivisorT>::mulh(x, y)
+        }
+
+        /// Returns the double-width product of `x` and `y`, as a pair of its top
+        /// and bottom halves.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::wide::", stringify!($mul_wide), ";")]
+        ///
+        #[doc = concat!("assert_eq!(", stringify!($mul_wide), "(", stringify!($BaseT), "::MAX, 3), (2, ", stringify!($BaseT), "::MAX - 2));")]
+        /// ```
+        #[inline]
+        pub const fn $mul_wide(x: $BaseT, y: $BaseT) -> ($BaseT, $BaseT) {
+            (<$DivisorT>::mulh(x, y), x.wrapping_mul(y))
+        }
+
+        /// Returns the quotient and remainder of dividing the double-width value
+        /// `(hi, lo)` by `d`, or `None` if `d` is zero or the quotient does not
+        /// fit into a single word, i.e. if `hi >= d`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::wide::", stringify!($div_wide), ";")]
+        ///
+        #[doc = concat!("assert_eq!(", stringify!($div_wide), "(2, ", stringify!($BaseT), "::MAX - 2, 3), Some((", stringify!($BaseT), "::MAX, 0)));")]
+        #[doc = concat!("assert_eq!(", stringify!($div_wide), "(3, 0, 3), None);")]
+        /// ```
+        #[inline]
+        pub const fn $div_wide(hi: $BaseT, lo: $BaseT, d: $BaseT) -> Option<($BaseT, $BaseT)> {
+            if hi >= d {
+                None
+            } else {
+                Some(<$DivisorT>::div_rem_wide_by_base(hi, lo, d))
+            }
+        }
+    };
+}
+
+macro_rules! wide_signed_fns {
+    (
+        $mulh:ident,
+        $mul_wide:ident,
+        $div_wide:ident,
+        $DivisorT:ident,
+        $UnsignedDivisorT:ident,
+        $BaseT:ident,
+        $UnsignedBaseT:ident
+    ) => {
+        /// Returns the top half of the double-width product of `x` and `y`.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::wide::", stringify!($mulh), ";")]
+        ///
+        #[doc = concat!("assert_eq!(", stringify!($mulh), "(", stringify!($BaseT), "::MIN, 3), -2);")]
+        /// ```
+        #[inline]
+        pub const fn $mulh(x: $BaseT, y: $BaseT) -> $BaseT {
+            <$DivisorT>::mulh(x, y)
+        }
+
+        /// Returns the double-width product of `x` and `y`, as a pair of its
+        /// signed top half and unsigned bottom half.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::wide::", stringify!($mul_wide), ";")]
+        ///
+        #[doc = concat!("assert_eq!(", stringify!($mul_wide), "(-1, 1), (-1, ", stringify!($UnsignedBaseT), "::MAX));")]
+        /// ```
+        #[inline]
+        #[allow(clippy::cast_sign_loss)]
+        pub const fn $mul_wide(x: $BaseT, y: $BaseT) -> ($BaseT, $UnsignedBaseT) {
+            (<$DivisorT>::mulh(x, y), x.wrapping_mul(y) as $UnsignedBaseT)
+        }
+
+        /// Returns the quotient and remainder of dividing the double-width value
+        /// `(hi, lo)` by `d`, rounding the quotient towards zero, or `None` if
+        /// `d` is zero or the quotient does not fit into a single word.
+        ///
+        /// As with the `%` operator, the remainder has the same sign as the
+        /// dividend.
+        ///
+        /// # Examples
+        /// ```
+        #[doc = concat!("use quickdiv::wide::{", stringify!($div_wide), ", ", stringify!($mul_wide), "};")]
+        ///
+        #[doc = concat!("let (hi, lo) = ", stringify!($mul_wide), "(", stringify!($BaseT), "::MIN, 3);")]
+        #[doc = concat!("assert_eq!(", stringify!($div_wide), "(hi, lo, 3), Some((", stringify!($BaseT), "::MIN, 0)));")]
+        #[doc = concat!("assert_eq!(", stringify!($div_wide), "(hi, lo, 2), None);")]
+        /// ```
+        #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        pub const fn $div_wide(hi: $BaseT, lo: $UnsignedBaseT, d: $BaseT) -> Option<($BaseT, $BaseT)> {
+            let negative = hi < 0;
+            let ud = d.unsigned_abs();
+
+            // Take the magnitude of the double-width dividend.
+            let (uhi, ulo) = if negative {
+                let ulo = (!lo).wrapping_add(1);
+                let uhi = (!(hi as $UnsignedBaseT)).wrapping_add((ulo == 0) as $UnsignedBaseT);
+                (uhi, ulo)
+            } else {
+                (hi as $UnsignedBaseT, lo)
+            };
+
+            if uhi >= ud {
+                return None;
+            }
+
+            let (q, r) = <$UnsignedDivisorT>::div_rem_wide_by_base(uhi, ulo, ud);
+            let (q, r) = (q as $BaseT, r as $BaseT);
+
+            if negative != (d < 0) {
+                if q < 0 && q != <$BaseT>::MIN {
+                    None
+                } else {
+                    Some((q.wrapping_neg(), if negative { -r } else { r }))
+                }
+            } else if q < 0 {
+                None
+            } else {
+                Some((q, if negative { -r } else { r }))
+            }
+        }
+    };
+}
+
+macro_rules! wide_unsigned_tests {
+    ($mulh:ident, $mul_wide:ident, $div_wide:ident, $BaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        mod $ModName {
+            use quickcheck::*;
+
+            use super::*;
+
+            reference_wide_impl! { $BaseT }
+
+            quickcheck! {
+                fn mul_wide_agrees_with_long_multiplication(x: $BaseT, y: $BaseT) -> bool {
+                    let expected = reference_mul_wide(x, y);
+                    $mul_wide(x, y) == expected && $mulh(x, y) == expected.0
+                }
+            }
+
+            quickcheck! {
+                fn div_wide_agrees_with_long_division(hi: $BaseT, lo: $BaseT, d: $BaseT) -> bool {
+                    let expected = if hi >= d {
+                        None
+                    } else {
+                        Some(reference_div_rem_wide(hi, lo, d))
+                    };
+                    $div_wide(hi, lo, d) == expected
+                }
+            }
+
+            quickcheck! {
+                fn div_wide_undoes_mul_wide(x: $BaseT, y: $BaseT) -> TestResult {
+                    if y == 0 {
+                        return TestResult::discard();
+                    }
+                    let (hi, lo) = $mul_wide(x, y);
+                    TestResult::from_bool($div_wide(hi, lo, y) == Some((x, 0)))
+                }
+            }
+        }
+    };
+}
+
+macro_rules! wide_signed_tests {
+    ($mulh:ident, $mul_wide:ident, $div_wide:ident, $BaseT:ident, $UnsignedBaseT:ident, $ModName:ident) => {
+        #[cfg(test)]
+        #[allow(clippy::cast_lossless, clippy::cast_possible_truncation, clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        mod $ModName {
+            use core::convert::TryFrom;
+            use quickcheck::*;
+
+            use super::*;
+
+            // Restricts 128-bit values to 64 bits, so that their products fit into an i128.
+            fn narrow(n: $BaseT) -> $BaseT {
+                if <$BaseT>::BITS > 64 {
+                    n as i64 as $BaseT
+                } else {
+                    n
+                }
+            }
+
+            quickcheck! {
+                fn mul_wide_agrees_with_builtin(x: $BaseT, y: $BaseT) -> bool {
+                    let (x, y) = (narrow(x), narrow(y));
+                    let p = (x as i128) * (y as i128);
+                    let hi = p.checked_shr(<$BaseT>::BITS).unwrap_or(p >> 127) as $BaseT;
+                    $mul_wide(x, y) == (hi, p as $UnsignedBaseT) && $mulh(x, y) == hi
+                }
+            }
+
+            quickcheck! {
+                fn div_wide_agrees_with_builtin(hi: $BaseT, lo: $UnsignedBaseT, d: $BaseT) -> TestResult {
+                    if <$BaseT>::BITS > 64 {
+                        return TestResult::discard();
+                    }
+
+                    let n = (hi as i128).checked_shl(<$BaseT>::BITS).unwrap_or(0) | (lo as i128);
+                    let expected = match (n.checked_div(d as i128), n.checked_rem(d as i128)) {
+                        (Some(q), Some(r)) => <$BaseT>::try_from(q).ok().map(|q| (q, r as $BaseT)),
+                        _ => None,
+                    };
+                    TestResult::from_bool($div_wide(hi, lo, d) == expected)
+                }
+            }
+
+            quickcheck! {
+                fn div_wide_undoes_mul_wide(x: $BaseT, y: $BaseT) -> TestResult {
+                    if y == 0 {
+                        return TestResult::discard();
+                    }
+                    let (hi, lo) = $mul_wide(x, y);
+                    TestResult::from_bool($div_wide(hi, lo, y) == Some((x, 0)))
+                }
+            }
+        }
+    };
+}
+
+wide_unsigned_fns! { mulh_u8, mul_wide_u8, div_wide_u8, DivisorU8, u8 }
+wide_unsigned_fns! { mulh_u16, mul_wide_u16, div_wide_u16, DivisorU16, u16 }
+wide_unsigned_fns! { mulh_u32, mul_wide_u32, div_wide_u32, DivisorU32, u32 }
+wide_unsigned_fns! { mulh_u64, mul_wide_u64, div_wide_u64, DivisorU64, u64 }
+wide_unsigned_fns! { mulh_u128, mul_wide_u128, div_wide_u128, DivisorU128, u128 }
+wide_unsigned_fns! { mulh_usize, mul_wide_usize, div_wide_usize, DivisorUsize, usize }
+
+wide_signed_fns! { mulh_i8, mul_wide_i8, div_wide_i8, DivisorI8, DivisorU8, i8, u8 }
+wide_signed_fns! { mulh_i16, mul_wide_i16, div_wide_i16, DivisorI16, DivisorU16, i16, u16 }
+wide_signed_fns! { mulh_i32, mul_wide_i32, div_wide_i32, DivisorI32, DivisorU32, i32, u32 }
+wide_signed_fns! { mulh_i64, mul_wide_i64, div_wide_i64, DivisorI64, DivisorU64, i64, u64 }
+wide_signed_fns! { mulh_i128, mul_wide_i128, div_wide_i128, DivisorI128, DivisorU128, i128, u128 }
+wide_signed_fns! { mulh_isize, mul_wide_isize, div_wide_isize, DivisorIsize, DivisorUsize, isize, usize }
+
+wide_unsigned_tests! { mulh_u8, mul_wide_u8, div_wide_u8, u8, wide_u8 }
+wide_unsigned_tests! { mulh_u16, mul_wide_u16, div_wide_u16, u16, wide_u16 }
+wide_unsigned_tests! { mulh_u32, mul_wide_u32, div_wide_u32, u32, wide_u32 }
+wide_unsigned_tests! { mulh_u64, mul_wide_u64, div_wide_u64, u64, wide_u64 }
+wide_unsigned_tests! { mulh_u128, mul_wide_u128, div_wide_u128, u128, wide_u128 }
+
+wide_signed_tests! { mulh_i8, mul_wide_i8, div_wide_i8, i8, u8, wide_i8 }
+wide_signed_tests! { mulh_i16, mul_wide_i16, div_wide_i16, i16, u16, wide_i16 }
+wide_signed_tests! { mulh_i32, mul_wide_i32, div_wide_i32, i32, u32, wide_i32 }
+wide_signed_tests! { mulh_i64, mul_wide_i64, div_wide_i64, i64, u64, wide_i64 }
+wide_signed_tests! { mulh_i128, mul_wide_i128, div_wide_i128, i128, u128, wide_i128 }